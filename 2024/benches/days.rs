@@ -0,0 +1,71 @@
+//! Times the parsing and both parts of every solved day on its puzzle input, or on its first
+//! example when the input is missing. A day is selected with its name, as in
+//! `cargo bench --bench days -- 2024/day11`, and the reports are written to
+//! `target/criterion/report/index.html`
+use std::hint::black_box;
+
+use advent_of_code::etc::registry::{self, DayInfo};
+use advent_of_code::input::{self, Inputs, Source};
+use advent_of_code::Context;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// The input of a day and where it comes from, `None` if there is neither a puzzle input nor
+/// an example
+fn read_input(info: &DayInfo) -> Option<(String, &'static str)> {
+    let (dir, examples_dir) = (input::default_dir(), input::default_examples_dir());
+
+    [(Source::Puzzle, "input"), (Source::Example(1), "example")]
+        .into_iter()
+        .find_map(|(source, kind)| {
+            let input = Inputs::new(&dir, &examples_dir, source)
+                .read(info.year, info.day)
+                .ok()?;
+            Some((input, kind))
+        })
+}
+
+fn days(c: &mut Criterion) {
+    let mut days: Vec<_> = registry::DAYS
+        .iter()
+        .filter(|info| info.is_solved())
+        .collect();
+    days.sort_by_key(|info| (info.year, info.day));
+
+    for info in days {
+        let Some((input, kind)) = read_input(info) else {
+            eprintln!("Day {:02} of {} has no input, skipped", info.day, info.year);
+            continue;
+        };
+        let parsed = match (info.parse)(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("Day {:02} of {} cannot be parsed: {e}", info.day, info.year);
+                continue;
+            }
+        };
+
+        // The input is part of the name, so that a run on the example is never compared with
+        // one on the puzzle input
+        let mut group = c.benchmark_group(format!("{}/day{:02}/{kind}", info.year, info.day));
+        group.bench_function("parse", |b| b.iter(|| (info.parse)(black_box(&input))));
+
+        // An example is often meant for a single part, the other one may fail on it. The default
+        // context draws nothing, so only the solving is timed
+        let ctx = Context::default();
+        if parsed.part1(&ctx).is_ok() {
+            group.bench_function("part1", |b| b.iter(|| parsed.part1(&ctx)));
+        }
+        if parsed.part2(&ctx).is_ok() {
+            group.bench_function("part2", |b| b.iter(|| parsed.part2(&ctx)));
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    // The slowest days take seconds, fewer samples keep a run of the whole calendar short
+    config = Criterion::default().sample_size(10);
+    targets = days
+}
+criterion_main!(benches);
@@ -0,0 +1,97 @@
+//! Compares implementations of the functions where some days spend most of their time. An
+//! alternative is added next to the current one in the list of its function, checked to give
+//! the same result, and both are drawn in the report of the function, linked from
+//! `target/criterion/report/index.html`
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use advent_of_code::days::{day07, day11};
+use advent_of_code::input::{self, Inputs, Source};
+use advent_of_code::Day;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+type Blink = fn(HashMap<u64, u64>) -> HashMap<u64, u64>;
+
+/// The implementations of [`day11::blink`]
+const BLINKS: &[(&str, Blink)] = &[("current", day11::blink)];
+
+type IsEquationValid = fn(&(u64, Vec<u64>), &[day07::Operator]) -> bool;
+
+/// The implementations of [`day07::is_equation_valid`]
+const IS_EQUATION_VALID: &[(&str, IsEquationValid)] = &[("current", day07::is_equation_valid)];
+
+/// The puzzle input of a day of 2024, or the example of its puzzle
+fn read_input(day: u8, example: &str) -> String {
+    Inputs::new(
+        &input::default_dir(),
+        &input::default_examples_dir(),
+        Source::Puzzle,
+    )
+    .read(2024, day)
+    .unwrap_or_else(|_| example.to_owned())
+}
+
+fn blink(c: &mut Criterion) {
+    let mut stones = day11::Day11::parse(&read_input(11, "125 17")).unwrap();
+    // The stones after a few blinks, when there are enough of them to matter
+    for _ in 0..25 {
+        stones = day11::blink(stones);
+    }
+
+    let expected = day11::blink(stones.clone());
+    let mut group = c.benchmark_group("day11::blink");
+    for (name, blink) in BLINKS {
+        assert_eq!(blink(stones.clone()), expected, "{name} blinks differently");
+        group.bench_with_input(BenchmarkId::from_parameter(name), &stones, |b, stones| {
+            b.iter_batched(
+                || stones.clone(),
+                |stones| blink(black_box(stones)),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn is_equation_valid(c: &mut Criterion) {
+    const EXAMPLE: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    use day07::Operator::*;
+    let equations = day07::Day07::parse(&read_input(7, EXAMPLE)).unwrap();
+    let operators = [Add, Multiply, Concatenate];
+    let valid = |is_equation_valid: IsEquationValid| {
+        equations
+            .iter()
+            .map(|equation| is_equation_valid(equation, &operators))
+            .collect::<Vec<_>>()
+    };
+
+    let expected = valid(day07::is_equation_valid);
+    let mut group = c.benchmark_group("day07::is_equation_valid");
+    for (name, is_equation_valid) in IS_EQUATION_VALID {
+        assert_eq!(
+            valid(*is_equation_valid),
+            expected,
+            "{name} validates differently"
+        );
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter(|| valid(black_box(*is_equation_valid)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = blink, is_equation_valid
+}
+criterion_main!(benches);
@@ -0,0 +1,142 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use advent_of_code::etc::{Part, Solution};
+use serde::{Deserialize, Serialize};
+
+/// The default file of the stored answers, inside the crate so that the runner finds it from
+/// any directory
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+/// Known answers keyed by year, day and part:
+/// ```toml
+/// [2024.day01]
+/// part1 = "11"
+/// part2 = "31"
+/// ```
+///
+/// The answers are stored as text so that they can be compared whatever the type of the
+/// [`Solution`]
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    years: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+/// Outcome of comparing a solution with the stored answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase", tag = "status")]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// There is no stored answer to compare with
+    Unknown,
+}
+
+impl Answers {
+    /// Loads the answers from a TOML file, a missing file has no answers
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(format!("Could not read {}: {e}", path.display())),
+        };
+
+        toml::from_str(&content).map_err(|e| format!("Could not parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content =
+            toml::to_string(self).map_err(|e| format!("Could not serialize the answers: {e}"))?;
+
+        fs::write(path, content).map_err(|e| format!("Could not write {}: {e}", path.display()))
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&str> {
+        self.years
+            .get(&year.to_string())?
+            .get(&day_key(day))?
+            .get(part_key(part))
+            .map(String::as_str)
+    }
+
+    pub fn set(&mut self, year: u16, day: u8, part: Part, solution: &Solution) {
+        self.years
+            .entry(year.to_string())
+            .or_default()
+            .entry(day_key(day))
+            .or_default()
+            .insert(part_key(part).to_owned(), solution.to_string());
+    }
+
+    pub fn verify(&self, year: u16, day: u8, part: Part, solution: &Solution) -> Verdict {
+        match self.get(year, day, part) {
+            Some(expected) if expected == solution.to_string() => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_owned(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+pub fn day_key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+pub fn part_key(part: Part) -> &'static str {
+    match part {
+        Part::One => "part1",
+        Part::Two => "part2",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn verify_answers() {
+        let mut answers = Answers::default();
+        answers.set(2024, 1, Part::One, &Solution::from(11_u64));
+
+        assert_eq!(
+            answers.verify(2024, 1, Part::One, &Solution::from(11_u32)),
+            Verdict::Correct
+        );
+        assert_eq!(
+            answers.verify(2024, 1, Part::One, &Solution::from(12_u64)),
+            Verdict::Wrong {
+                expected: String::from("11")
+            }
+        );
+        assert_eq!(
+            answers.verify(2024, 1, Part::Two, &Solution::from(11_u64)),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.verify(2023, 1, Part::One, &Solution::from(11_u64)),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn toml_layout() {
+        let mut answers = Answers::default();
+        answers.set(2024, 1, Part::One, &Solution::from(11_u64));
+        answers.set(2024, 1, Part::Two, &Solution::from("abc"));
+
+        let content = toml::to_string(&answers).unwrap();
+        assert_eq!(content, "[2024.day01]\npart1 = \"11\"\npart2 = \"abc\"\n");
+
+        assert_eq!(toml::from_str::<Answers>(&content).unwrap(), answers);
+    }
+}
@@ -0,0 +1,332 @@
+use std::time::Duration;
+
+use advent_of_code::etc::cancel::{self, CancelToken};
+use advent_of_code::etc::{AocError, Context, DayRunner, Part};
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+
+use crate::report::as_ms;
+
+/// Summary of the timings of a stage over multiple runs, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Stats::default();
+        }
+
+        let mut samples: Vec<f64> = samples.iter().map(|&d| as_ms(d)).collect();
+        samples.sort_by(|a, b| a.total_cmp(b));
+
+        let len = samples.len();
+        let median = match len % 2 {
+            0 => (samples[len / 2 - 1] + samples[len / 2]) / 2.,
+            _ => samples[len / 2],
+        };
+        let mean = samples.iter().sum::<f64>() / len as f64;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / len as f64;
+
+        Stats {
+            min: samples[0],
+            median,
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+/// The timings of every stage of a day, the parts that were not run are `None`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayBench {
+    pub year: u16,
+    pub day: u8,
+    pub runs: u32,
+    pub parse: Stats,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+    pub total: Stats,
+    /// The parts left out because they did not finish within the timeout
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timed_out: Vec<Part>,
+}
+
+/// How the days are benchmarked
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Number of measured solves
+    pub runs: u32,
+    /// Number of solves before measuring
+    pub warmup: u32,
+    /// Only solve one part
+    pub part: Option<Part>,
+    /// Longest time a solve may take, a part that never finishes is left out
+    pub timeout: Duration,
+}
+
+/// Solves a day `warmup` times without measuring it, then `runs` times collecting the timings
+/// of each stage. The input must already be loaded so that file I/O is not measured.
+///
+/// Each part is first solved once on its own, the ones that do not finish within the timeout
+/// are left out of the timings and reported in [`DayBench::timed_out`]. Fails with
+/// [`AocError::TimedOut`] if no part is left, or if a later solve exceeds the timeout. Every
+/// solve gets its own cancel token and the visualizer and artifacts of `ctx`
+pub fn bench_day(
+    year: u16,
+    day: u8,
+    func: DayRunner,
+    input: &str,
+    options: BenchOptions,
+    ctx: &Context,
+) -> Result<DayBench, AocError> {
+    let BenchOptions {
+        runs,
+        warmup,
+        part,
+        timeout,
+    } = options;
+
+    let run = |part| {
+        let ctx = Context {
+            cancel: CancelToken::new(),
+            ..ctx.clone()
+        };
+        cancel::run_with_timeout(func, input, part, Some(timeout), &ctx)
+    };
+
+    let requested = match part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+    let mut timed_out = vec![];
+    for &part in &requested {
+        match run(Some(part)) {
+            Err(AocError::TimedOut(_)) => timed_out.push(part),
+            result => {
+                result?;
+            }
+        }
+    }
+    let part = match requested.len() - timed_out.len() {
+        0 => return Err(AocError::TimedOut(timeout)),
+        1 => requested.into_iter().find(|part| !timed_out.contains(part)),
+        _ => None,
+    };
+
+    let solve = || run(part);
+    for _ in 0..warmup {
+        solve()?;
+    }
+
+    let mut parse = Vec::with_capacity(runs as usize);
+    let mut part1 = Vec::with_capacity(runs as usize);
+    let mut part2 = Vec::with_capacity(runs as usize);
+    let mut total = Vec::with_capacity(runs as usize);
+
+    for _ in 0..runs {
+        let day_run = solve()?;
+
+        parse.push(day_run.parse);
+        part1.extend(day_run.part1.as_ref().map(|p| p.elapsed));
+        part2.extend(day_run.part2.as_ref().map(|p| p.elapsed));
+        total.push(day_run.elapsed());
+    }
+
+    Ok(DayBench {
+        year,
+        day,
+        runs,
+        parse: Stats::new(&parse),
+        part1: (!part1.is_empty()).then(|| Stats::new(&part1)),
+        part2: (!part2.is_empty()).then(|| Stats::new(&part2)),
+        total: Stats::new(&total),
+        timed_out,
+    })
+}
+
+impl DayBench {
+    pub fn print(&self) {
+        println!(
+            "\n=== {} Day {:02} === ({} runs)",
+            self.year, self.day, self.runs
+        );
+        println!(
+            "  {:<8}{:>12}{:>12}{:>12}{:>12}",
+            "Stage".bold(),
+            "Min".bold(),
+            "Median".bold(),
+            "Mean".bold(),
+            "Stddev".bold()
+        );
+
+        let stages = [
+            ("Parse", Some(&self.parse)),
+            ("Part 1", self.part1.as_ref()),
+            ("Part 2", self.part2.as_ref()),
+            ("Total", Some(&self.total)),
+        ];
+        for (name, stats) in stages {
+            if let Some(stats) = stats {
+                println!(
+                    "  {:<8}{:>9.4} ms{:>9.4} ms{:>9.4} ms{:>9.4} ms",
+                    name, stats.min, stats.median, stats.mean, stats.stddev
+                );
+            }
+        }
+        for part in &self.timed_out {
+            println!(
+                "  {}",
+                format!("Part {} timed out, it was not measured", part.number()).yellow()
+            );
+        }
+    }
+}
+
+/// Prints the median of every stage of each day and their sum
+pub fn print_summary(benches: &[DayBench]) {
+    println!(
+        "\n{:<9}{:>14}{:>14}{:>14}{:>14}",
+        "Day".bold(),
+        "Parse".bold(),
+        "Part 1".bold(),
+        "Part 2".bold(),
+        "Total".bold()
+    );
+
+    let median = |stats: Option<&Stats>| match stats {
+        Some(stats) => format!("{:.4} ms", stats.median),
+        None => String::from("-"),
+    };
+
+    for bench in benches {
+        let part = |part: Part, stats: Option<&Stats>| match bench.timed_out.contains(&part) {
+            true => String::from("timed out"),
+            false => median(stats),
+        };
+        println!(
+            "{:<9}{:>14}{:>14}{:>14}{:>14}",
+            format!("{} {:02}", bench.year, bench.day),
+            median(Some(&bench.parse)),
+            part(Part::One, bench.part1.as_ref()),
+            part(Part::Two, bench.part2.as_ref()),
+            median(Some(&bench.total)),
+        );
+    }
+
+    let total: f64 = benches.iter().map(|b| b.total.median).sum();
+    println!("{:<9}{:>56}", "Sum".bold(), format!("{:.4} ms", total));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use advent_of_code::etc::{DayRun, PartRun, Solution};
+
+    fn ms(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|&s| Duration::from_millis(s)).collect()
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::new(&ms(&[4, 1, 3, 2]));
+        assert_eq!(stats.min, 1.);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.mean, 2.5);
+        assert!((stats.stddev - 1.25_f64.sqrt()).abs() < 1e-9);
+
+        let stats = Stats::new(&ms(&[5, 1, 3]));
+        assert_eq!(stats.median, 3.);
+
+        assert_eq!(Stats::new(&[]), Stats::default());
+    }
+
+    #[test]
+    fn bench_single_part() {
+        fn runner(_input: &str, part: Option<Part>, _ctx: &Context) -> Result<DayRun, AocError> {
+            let part_run = PartRun {
+                solution: Solution::from(0_u8),
+                elapsed: Duration::from_millis(2),
+                alloc: None,
+            };
+
+            Ok(DayRun {
+                parse: Duration::from_millis(1),
+                parse_alloc: None,
+                part1: part.is_none().then(|| part_run.clone()),
+                part2: Some(part_run),
+            })
+        }
+
+        let bench = bench_day(
+            2024,
+            1,
+            runner,
+            "",
+            options(Some(Part::Two)),
+            &Context::default(),
+        )
+        .unwrap();
+        assert_eq!(bench.runs, 3);
+        assert_eq!(bench.parse.median, 1.);
+        assert_eq!(bench.part1, None);
+        assert_eq!(bench.part2.unwrap().median, 2.);
+        assert_eq!(bench.total.median, 3.);
+        assert!(bench.timed_out.is_empty());
+    }
+
+    fn options(part: Option<Part>) -> BenchOptions {
+        BenchOptions {
+            runs: 3,
+            warmup: 1,
+            part,
+            timeout: Duration::from_millis(50),
+        }
+    }
+
+    #[test]
+    fn leave_out_parts_that_time_out() {
+        /// The second part only stops once cancelled
+        fn runner(_input: &str, part: Option<Part>, ctx: &Context) -> Result<DayRun, AocError> {
+            if part != Some(Part::One) {
+                while !ctx.cancel.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                return Err(AocError::Cancelled);
+            }
+
+            Ok(DayRun {
+                parse: Duration::from_millis(1),
+                parse_alloc: None,
+                part1: Some(PartRun {
+                    solution: Solution::from(0_u8),
+                    elapsed: Duration::from_millis(2),
+                    alloc: None,
+                }),
+                part2: None,
+            })
+        }
+
+        let bench = bench_day(2024, 14, runner, "", options(None), &Context::default()).unwrap();
+        assert_eq!(bench.timed_out, [Part::Two]);
+        assert_eq!(bench.part1.unwrap().median, 2.);
+        assert_eq!(bench.part2, None);
+
+        assert!(matches!(
+            bench_day(
+                2024,
+                14,
+                runner,
+                "",
+                options(Some(Part::Two)),
+                &Context::default()
+            ),
+            Err(AocError::TimedOut(_))
+        ));
+    }
+}
@@ -0,0 +1,563 @@
+use std::{
+    io::{self, Read},
+    path::PathBuf,
+    time::Duration,
+};
+
+use advent_of_code::etc::artifacts::{self, Retention};
+use advent_of_code::etc::visualize::Mode;
+use advent_of_code::etc::Part;
+use advent_of_code::input::{self, Source};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use tracing_subscriber::filter::LevelFilter;
+
+use crate::report::Format;
+use crate::selector::{parse_day, DaySelector};
+use crate::{answers, client, submit};
+
+/// Runner for the Advent of Code solutions
+#[derive(Parser, Debug)]
+#[command(version, about, arg_required_else_help = true, after_help = DAYS_HELP)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// Years of the puzzles, separated by commas. The days are selected in each of them and
+    /// reported together
+    #[arg(
+        long = "year",
+        value_name = "YEAR",
+        global = true,
+        env = "AOC_YEAR",
+        value_delimiter = ',',
+        value_parser = clap::value_parser!(u16).range(2015..),
+        default_values_t = [advent_of_code::DEFAULT_YEAR]
+    )]
+    pub years: Vec<u16>,
+
+    /// Directory with the puzzle inputs, named `YYYY/dayNN.txt`
+    #[arg(long, global = true, env = "AOC_INPUT_DIR", default_value_os_t = input::default_dir())]
+    pub input_dir: PathBuf,
+
+    /// Directory with the example inputs, named `YYYY/dayNN/N.txt`
+    #[arg(long, global = true, env = "AOC_EXAMPLES_DIR", default_value_os_t = input::default_examples_dir())]
+    pub examples_dir: PathBuf,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    #[command(flatten)]
+    pub log: LogArgs,
+}
+
+const DAYS_HELP: &str = "\
+DAYS:
+  N          a single day, e.g. `7`
+  A..=B      the days from A to B included, `A..B` excludes B, `A..` and `..=B` are open
+  all        every day of the calendar
+  solved     every day that is not a stub
+  !DAYS      excludes the days, e.g. `!14` (quote it in the shell). If only exclusions
+             are given they apply to the solved days
+The days are selected in each year given with `--year`";
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Solve the given days using their input file
+    #[command(after_help = DAYS_HELP)]
+    Run {
+        /// Days to run, see `DAYS` below
+        #[arg(required = true)]
+        days: Vec<DaySelector>,
+
+        /// Only solve one part
+        #[arg(short, long)]
+        part: Option<Part>,
+
+        /// Output format of the report
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Compare the answers with the stored ones and fail if any is wrong
+        #[arg(long)]
+        verify: bool,
+
+        /// Store the answers of the solved days, replacing the previous ones
+        #[arg(long, conflicts_with = "verify")]
+        record: bool,
+
+        /// File with the stored answers
+        #[arg(long, default_value_os_t = answers::default_path())]
+        answers: PathBuf,
+
+        /// Stop a day that runs for longer, e.g. `30s`, `500ms` or `2m`
+        #[arg(short, long, value_parser = parse_duration)]
+        timeout: Option<Duration>,
+
+        /// Number of days solved at the same time, the results are still printed in day order
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+
+        /// Show how the days are solved, in the terminal when no mode is given
+        #[arg(long, value_enum, value_name = "MODE", num_args = 0..=1, default_value_t, default_missing_value = "terminal")]
+        visualize: Mode,
+    },
+
+    /// Run the unit tests of the given days, or all of them if none is given
+    #[command(after_help = DAYS_HELP)]
+    Test {
+        /// Days to test, see `DAYS` below
+        days: Vec<DaySelector>,
+    },
+
+    /// Solve the given days multiple times and report statistics on the runtime of each stage
+    #[command(after_help = DAYS_HELP)]
+    Bench {
+        /// Days to benchmark, see `DAYS` below
+        #[arg(required = true)]
+        days: Vec<DaySelector>,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Number of times each day is solved
+        #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+
+        /// Number of times each day is solved before measuring
+        #[arg(short, long, default_value_t = 3)]
+        warmup: u32,
+
+        /// Only solve one part
+        #[arg(short, long)]
+        part: Option<Part>,
+
+        /// Longest time a solve may take, e.g. `30s`, `500ms` or `2m`. A part that does not
+        /// finish in time is reported as timed out instead of being measured
+        #[arg(short, long, value_parser = parse_duration, default_value = "30s")]
+        timeout: Duration,
+
+        #[command(flatten)]
+        history: HistoryArgs,
+    },
+
+    /// Download the puzzle inputs of the given days to the input directory, skipping the
+    /// ones already there
+    #[command(after_help = DAYS_HELP)]
+    Fetch {
+        /// Days to download, see `DAYS` below
+        #[arg(required = true)]
+        days: Vec<DaySelector>,
+
+        #[command(flatten)]
+        site: SiteArgs,
+
+        /// Minimum time between two requests
+        #[arg(long, value_parser = parse_duration, default_value = "2s")]
+        delay: Duration,
+    },
+
+    /// Solve a part on the puzzle input and submit the answer. Rejected answers are remembered
+    /// and never submitted again
+    Submit {
+        /// Day to submit
+        #[arg(value_parser = parse_day)]
+        day: u8,
+
+        /// Part to submit
+        part: Part,
+
+        #[command(flatten)]
+        site: SiteArgs,
+
+        /// File with the stored answers, where an accepted answer is recorded
+        #[arg(long, default_value_os_t = answers::default_path())]
+        answers: PathBuf,
+
+        /// File with the rejected answers and the time to wait before the next submission
+        #[arg(long, default_value_os_t = submit::default_path())]
+        submissions: PathBuf,
+    },
+
+    /// List the days known to the runner
+    List,
+
+    /// Open a dashboard of the registered days, where a day, its tests or its visualization run
+    /// on a keypress
+    Dashboard {
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// File with the stored answers, compared with the answers of the puzzle inputs
+        #[arg(long, default_value_os_t = answers::default_path())]
+        answers: PathBuf,
+
+        /// Stop a day that runs for longer, e.g. `30s`, `500ms` or `2m`. A day left running
+        /// would keep its thread busy until the dashboard closes
+        #[arg(short, long, value_parser = parse_duration, default_value = "30s")]
+        timeout: Duration,
+    },
+
+    /// Rerun the tests and the solver of a day whenever its source, its input or its examples
+    /// change, and compare the answers with the previous ones
+    Watch {
+        /// Day to watch
+        #[arg(value_parser = parse_day)]
+        day: u8,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Only rerun the solver
+        #[arg(long)]
+        no_test: bool,
+
+        /// Time between two checks of the files
+        #[arg(long, value_parser = parse_duration, default_value = "500ms")]
+        interval: Duration,
+    },
+
+    /// Create the source file of a day with a test module, register it and add an empty
+    /// example. A day that is not a stub anymore is never replaced
+    New {
+        /// Day to create
+        #[arg(value_parser = parse_day)]
+        day: u8,
+
+        /// Title of the puzzle, shown by `list`. A day that is already registered keeps its title
+        #[arg(long)]
+        title: Option<String>,
+    },
+}
+
+/// Where the input of the days is read from, by default `dayNN.txt` in the input directory
+#[derive(Args, Debug)]
+pub struct InputArgs {
+    /// Read the input of the day from this file instead
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["stdin", "example"])]
+    pub input: Option<PathBuf>,
+
+    /// Read the input of the day from the standard input
+    #[arg(long, conflicts_with = "example")]
+    pub stdin: bool,
+
+    /// Solve the example `N` of each day instead of the puzzle input
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "1")]
+    pub example: Option<u8>,
+}
+
+impl InputArgs {
+    /// Where the input comes from, the standard input is read right away
+    pub fn source(&self) -> Result<Source, String> {
+        let source = match (&self.input, self.stdin, self.example) {
+            (Some(path), _, _) => Source::File(path.clone()),
+            (_, true, _) => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("Could not read the standard input: {e}"))?;
+                Source::Stdin(input)
+            }
+            (_, _, Some(example)) => Source::Example(example),
+            _ => Source::Puzzle,
+        };
+
+        Ok(source)
+    }
+}
+
+/// How to reach the Advent of Code website
+#[derive(Args, Debug)]
+pub struct SiteArgs {
+    /// File with the session token, used when `AOC_SESSION` is not set
+    #[arg(long, value_name = "PATH", default_value_os_t = client::default_session_file())]
+    pub session_file: PathBuf,
+
+    /// Address of the website
+    #[arg(long, env = "AOC_URL", default_value = client::DEFAULT_URL)]
+    pub url: String,
+}
+
+/// Options of the bench history, stored in `target/aoc-bench/history.jsonl`
+#[derive(Args, Debug)]
+pub struct HistoryArgs {
+    /// Commit to compare with, a prefix is enough. Defaults to the latest benchmark of each day
+    #[arg(long, value_name = "COMMIT")]
+    pub baseline: Option<String>,
+
+    /// Slowdown of the median total time, in percent, above which a day is a regression
+    #[arg(long, value_name = "PERCENT", default_value_t = 10.)]
+    pub threshold: f64,
+
+    /// Do not add this run to the history
+    #[arg(long)]
+    pub no_save: bool,
+}
+
+/// Where the days write the files they produce, one directory per run
+#[derive(Args, Debug)]
+pub struct OutputArgs {
+    /// Directory of the runs, where the files of a day go to `<run>/YYYY/dayNN`
+    #[arg(long, global = true, env = "AOC_OUT_DIR", default_value_os_t = artifacts::default_dir())]
+    pub out_dir: PathBuf,
+
+    /// Number of runs kept in the output directory, the oldest ones are removed once a run
+    /// ends. `all` keeps every run
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        env = "AOC_KEEP_RUNS",
+        default_value_t
+    )]
+    pub keep_runs: Retention,
+}
+
+/// What is logged to the standard error and how
+#[derive(Args, Debug)]
+pub struct LogArgs {
+    /// Most detailed level logged, replacing the directives of `RUST_LOG`. Defaults to `info`
+    #[arg(
+        long,
+        global = true,
+        value_name = "LEVEL",
+        value_parser = PossibleValuesParser::new(["off", "error", "warn", "info", "debug", "trace"])
+            .map(|level| level.parse::<LevelFilter>().expect("the level is a possible value"))
+    )]
+    pub log_level: Option<LevelFilter>,
+
+    /// Format of the logs
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub log_format: LogFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// One line per event, with the spans it belongs to
+    #[default]
+    Text,
+    /// One JSON object per event
+    Json,
+}
+
+/// Parses a duration made of a number and a unit among `ms`, `s` and `m`, seconds by default
+fn parse_duration(arg: &str) -> Result<Duration, String> {
+    let split = arg
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(arg.len());
+    let (value, unit) = arg.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("`{arg}` does not start with a number"))?;
+    let seconds = match unit {
+        "ms" => value / 1000.,
+        "" | "s" => value,
+        "m" => value * 60.,
+        _ => return Err(format!("`{unit}` is not a unit, expected `ms`, `s` or `m`")),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("`{arg}` is not a duration: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn run_requires_days() {
+        assert!(Cli::try_parse_from(["aoc", "run"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "1", "--verify", "--record"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "1", "--jobs", "0"]).is_err());
+
+        let cli = Cli::try_parse_from(["aoc", "run", "1", "!12", "all"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Run { days, .. } if days == vec![
+                DaySelector::Day(1),
+                DaySelector::Exclude(Box::new(DaySelector::Day(12))),
+                DaySelector::All,
+            ]
+        ));
+    }
+
+    #[test]
+    fn input_sources() {
+        let cli = Cli::try_parse_from(["aoc", "run", "1", "--example"]).unwrap();
+        assert!(matches!(cli.command, Command::Run { input, .. } if input.example == Some(1)));
+
+        let cli = Cli::try_parse_from(["aoc", "bench", "1", "--example", "3"]).unwrap();
+        assert!(matches!(cli.command, Command::Bench { input, .. } if input.example == Some(3)));
+
+        let cli = Cli::try_parse_from(["aoc", "list", "--input-dir", "/tmp/aoc"]).unwrap();
+        assert_eq!(cli.input_dir, PathBuf::from("/tmp/aoc"));
+
+        assert!(Cli::try_parse_from(["aoc", "run", "1", "--stdin", "--example"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "1", "--stdin", "--input", "a"]).is_err());
+    }
+
+    #[test]
+    fn visualize_mode() {
+        let cli = Cli::try_parse_from(["aoc", "run", "1"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Run {
+                visualize: Mode::None,
+                ..
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["aoc", "run", "1", "--visualize"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Run {
+                visualize: Mode::Terminal,
+                ..
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["aoc", "run", "1", "--visualize", "file"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Run {
+                visualize: Mode::File,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn output_options() {
+        let cli = Cli::try_parse_from(["aoc", "run", "14"]).unwrap();
+        assert_eq!(cli.output.out_dir, artifacts::default_dir());
+        assert_eq!(cli.output.keep_runs, Retention::Last(10));
+
+        let cli = Cli::try_parse_from([
+            "aoc",
+            "run",
+            "14",
+            "--out-dir",
+            "/tmp/aoc-out",
+            "--keep-runs",
+            "all",
+        ])
+        .unwrap();
+        assert_eq!(cli.output.out_dir, PathBuf::from("/tmp/aoc-out"));
+        assert_eq!(cli.output.keep_runs, Retention::All);
+
+        assert!(Cli::try_parse_from(["aoc", "run", "14", "--keep-runs", "0"]).is_err());
+    }
+
+    #[test]
+    fn log_options() {
+        let cli = Cli::try_parse_from(["aoc", "list"]).unwrap();
+        assert_eq!(cli.log.log_level, None);
+        assert_eq!(cli.log.log_format, LogFormat::Text);
+
+        let cli = Cli::try_parse_from([
+            "aoc",
+            "run",
+            "1",
+            "--log-level",
+            "debug",
+            "--log-format",
+            "json",
+        ])
+        .unwrap();
+        assert_eq!(cli.log.log_level, Some(LevelFilter::DEBUG));
+        assert_eq!(cli.log.log_format, LogFormat::Json);
+
+        assert!(Cli::try_parse_from(["aoc", "list", "--log-level", "loud"]).is_err());
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("10h").is_err());
+    }
+
+    #[test]
+    fn bench_history() {
+        let cli = Cli::try_parse_from([
+            "aoc",
+            "bench",
+            "1",
+            "--baseline",
+            "2fe9",
+            "--threshold",
+            "5",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Bench { history, .. }
+                if history.baseline.as_deref() == Some("2fe9") && history.threshold == 5. && !history.no_save
+        ));
+    }
+
+    #[test]
+    fn years() {
+        let cli = Cli::try_parse_from(["aoc", "run", "1"]).unwrap();
+        assert_eq!(cli.years, [advent_of_code::DEFAULT_YEAR]);
+
+        let cli = Cli::try_parse_from(["aoc", "run", "1", "--year", "2023,2024"]).unwrap();
+        assert_eq!(cli.years, [2023, 2024]);
+
+        let cli = Cli::try_parse_from(["aoc", "--year", "2023", "list"]).unwrap();
+        assert_eq!(cli.years, [2023]);
+
+        assert!(Cli::try_parse_from(["aoc", "run", "1", "--year", "2014"]).is_err());
+    }
+
+    #[test]
+    fn answers_file() {
+        let cli = Cli::try_parse_from(["aoc", "run", "1", "--verify"]).unwrap();
+        let Command::Run { answers, .. } = cli.command else {
+            panic!("not a run: {:?}", cli.command);
+        };
+        assert_eq!(answers, answers::default_path());
+        assert!(answers.is_absolute());
+
+        let cli = Cli::try_parse_from(["aoc", "dashboard", "--answers", "a.toml"]).unwrap();
+        let Command::Dashboard { answers, .. } = cli.command else {
+            panic!("not a dashboard: {:?}", cli.command);
+        };
+        assert_eq!(answers, PathBuf::from("a.toml"));
+    }
+
+    #[test]
+    fn submit_part() {
+        let cli = Cli::try_parse_from(["aoc", "submit", "3", "2"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Submit {
+                day: 3,
+                part: Part::Two,
+                ..
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["aoc", "submit", "3", "1"]).unwrap();
+        let Command::Submit { submissions, .. } = cli.command else {
+            panic!("not a submission: {:?}", cli.command);
+        };
+        assert_eq!(submissions, submit::default_path());
+        assert!(submissions.is_absolute());
+
+        assert!(Cli::try_parse_from(["aoc", "submit", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "submit", "3", "3"]).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use advent_of_code::etc::Part;
+use ureq::{http::StatusCode, Agent};
+
+/// Environment variable with the session token, the `session` cookie of the website
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable with an email or a URL appended to the User-Agent, so that the
+/// maintainers of the website can reach out if the requests cause trouble
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// Client for the Advent of Code website, waiting between requests to not overload it
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    throttle: Throttle,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, interval: Duration) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(user_agent(env::var(CONTACT_VAR).ok().as_deref()))
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Client {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            throttle: Throttle::new(interval),
+        }
+    }
+
+    /// Downloads the input of a day
+    pub fn input(&mut self, year: u16, day: u8) -> Result<String, String> {
+        self.throttle.wait();
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let mut response = self
+            .agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| format!("Could not download {url}: {e}"))?;
+
+        match response.status() {
+            StatusCode::OK => response
+                .body_mut()
+                .read_to_string()
+                .map_err(|e| format!("Could not read the input of day {:02}: {e}", day)),
+            StatusCode::NOT_FOUND => Err(format!("Day {:02} is not unlocked yet", day)),
+            StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Err(format!(
+                    "The session token was refused, it may have expired (HTTP {})",
+                    response.status()
+                ))
+            }
+            status => Err(format!(
+                "Could not download the input of day {:02}: HTTP {status}",
+                day
+            )),
+        }
+    }
+
+    /// Posts the answer of a part, and returns the page telling whether it was right
+    pub fn submit(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> Result<String, String> {
+        self.throttle.wait();
+
+        let level = part.number().to_string();
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let mut response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .map_err(|e| format!("Could not submit to {url}: {e}"))?;
+
+        match response.status() {
+            StatusCode::OK => response
+                .body_mut()
+                .read_to_string()
+                .map_err(|e| format!("Could not read the answer to the submission: {e}")),
+            StatusCode::NOT_FOUND => Err(format!("Day {:02} is not unlocked yet", day)),
+            StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Err(format!(
+                    "The session token was refused, it may have expired (HTTP {})",
+                    response.status()
+                ))
+            }
+            status => Err(format!(
+                "Could not submit the answer of day {:02}: HTTP {status}",
+                day
+            )),
+        }
+    }
+}
+
+/// Outcome of [`fetch_input`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// The input was already on disk, it is never downloaded again
+    Cached(PathBuf),
+}
+
+/// Downloads the input of a day to `path` unless it already exists
+pub fn fetch_input(
+    client: &mut Client,
+    path: &Path,
+    year: u16,
+    day: u8,
+) -> Result<Fetched, String> {
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(Fetched::Cached(path.to_owned()));
+    }
+
+    let input = client.input(year, day)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+    }
+    // Written next to the destination then renamed, so that an interrupted download never
+    // leaves a truncated input that would be taken for a cached one
+    let partial = path.with_extension("txt.part");
+    fs::write(&partial, input)
+        .map_err(|e| format!("Could not write {}: {e}", partial.display()))?;
+    fs::rename(&partial, path).map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+
+    Ok(Fetched::Downloaded(path.to_owned()))
+}
+
+/// Finds the session token in [`SESSION_VAR`], or else in the first line of `session_file`
+pub fn session(session_file: &Path) -> Result<String, String> {
+    if let Some(session) = env::var(SESSION_VAR).ok().filter(|s| !s.trim().is_empty()) {
+        return Ok(session.trim().to_owned());
+    }
+
+    let content = fs::read_to_string(session_file).map_err(|e| {
+        format!(
+            "No session token: set {SESSION_VAR} or write it to {} ({e})",
+            session_file.display()
+        )
+    })?;
+
+    match content.lines().next().map(str::trim) {
+        Some(session) if !session.is_empty() => Ok(session.to_owned()),
+        _ => Err(format!("{} is empty", session_file.display())),
+    }
+}
+
+/// The default session file, `.adventofcode.session` in the home directory
+pub fn default_session_file() -> PathBuf {
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(".adventofcode.session")
+}
+
+fn user_agent(contact: Option<&str>) -> String {
+    let agent = concat!(
+        env!("CARGO_PKG_NAME"),
+        "/",
+        env!("CARGO_PKG_VERSION"),
+        " (personal Advent of Code runner"
+    );
+
+    match contact {
+        Some(contact) => format!("{agent}; {contact})"),
+        None => format!("{agent})"),
+    }
+}
+
+/// Waits so that two calls to [`Throttle::wait`] are at least `interval` apart
+struct Throttle {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl Throttle {
+    fn new(interval: Duration) -> Self {
+        Throttle {
+            interval,
+            last: None,
+        }
+    }
+
+    fn wait(&mut self) {
+        if let Some(elapsed) = self.last.map(|last| last.elapsed()) {
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+
+        self.last = Some(Instant::now());
+    }
+}
+
+/// A local HTTP server answering with canned responses, to test the client without the website
+#[cfg(test)]
+pub mod stub {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    /// A request received by the server
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// Answers each connection with the next `(status, body)`, and returns the base URL of the
+    /// server with the requests it received
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut parts = line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_owned();
+                let path = parts.next().unwrap_or_default().to_owned();
+
+                let mut headers = vec![];
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    match line.trim_end().split_once(": ") {
+                        Some((key, value)) => headers.push((key.to_owned(), value.to_owned())),
+                        None => break,
+                    }
+                }
+
+                let length = headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                    .map_or(0, |(_, value)| value.parse().unwrap());
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+
+                let _ = sender.send(Request {
+                    method,
+                    path,
+                    headers,
+                    body: String::from_utf8(request_body).unwrap(),
+                });
+            }
+        });
+
+        (url, receiver)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn download_once() {
+        let (url, requests) = stub::serve(vec![(200, "1 2\n3 4\n")]);
+        let mut client = Client::new(&url, "abc", Duration::ZERO);
+        let dir = temp_dir("fetch");
+        let path = dir.join("day01.txt");
+
+        assert_eq!(
+            fetch_input(&mut client, &path, 2024, 1),
+            Ok(Fetched::Downloaded(path.clone()))
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.body, "");
+        assert_eq!(request.path, "/2024/day/1/input");
+        assert_eq!(request.header("cookie"), Some("session=abc"));
+        assert!(request
+            .header("user-agent")
+            .is_some_and(|agent| agent.starts_with("advent_of_code/")));
+
+        // The stub only answers once, a second download would fail
+        assert_eq!(
+            fetch_input(&mut client, &path, 2024, 1),
+            Ok(Fetched::Cached(path.clone()))
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn download_errors() {
+        let (url, _requests) = stub::serve(vec![(404, "Not found"), (400, "Bad request")]);
+        let mut client = Client::new(&url, "abc", Duration::ZERO);
+        let dir = temp_dir("fetch-errors");
+        let path = dir.join("day25.txt");
+
+        assert_eq!(
+            fetch_input(&mut client, &path, 2024, 25),
+            Err(String::from("Day 25 is not unlocked yet"))
+        );
+        assert!(fetch_input(&mut client, &path, 2024, 25)
+            .is_err_and(|e| e.starts_with("The session token was refused")));
+        assert!(!path.exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn throttle() {
+        let mut throttle = Throttle::new(Duration::from_millis(30));
+        let start = Instant::now();
+
+        throttle.wait();
+        assert!(start.elapsed() < Duration::from_millis(30));
+        throttle.wait();
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn agent() {
+        assert!(user_agent(None).ends_with("(personal Advent of Code runner)"));
+        assert!(user_agent(Some("me@example.com")).ends_with("runner; me@example.com)"));
+    }
+}
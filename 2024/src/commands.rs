@@ -0,0 +1,804 @@
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+use advent_of_code::etc::artifacts::{ArtifactRun, Retention};
+use advent_of_code::etc::cancel::{self, CancelToken};
+use advent_of_code::etc::registry::{self, Status};
+use advent_of_code::etc::visualize::Mode;
+use advent_of_code::etc::{AocError, Context, DayRunner, Part, Solution, Visualizer};
+use advent_of_code::input::{self, Inputs, Source};
+use advent_of_code::{external, DEFAULT_YEAR};
+use owo_colors::OwoColorize;
+use tracing::{debug, info_span, warn};
+
+use crate::answers::Answers;
+use crate::bench::BenchOptions;
+use crate::cli::{Cli, Command, HistoryArgs, InputArgs, OutputArgs, SiteArgs};
+use crate::client::{self, Fetched};
+use crate::report::{self, DayReport, Format, Report};
+use crate::selector::{self, DaySelector};
+use crate::{bench, dashboard, history, pool, scaffold, submit, watch};
+
+/// Runs the command given on the command line
+pub fn execute(cli: Cli) -> Result<(), String> {
+    let Cli {
+        command,
+        years,
+        input_dir,
+        examples_dir,
+        output,
+        log: _,
+    } = cli;
+
+    match command {
+        Command::Run {
+            days,
+            part,
+            format,
+            verify,
+            record,
+            input,
+            answers,
+            timeout,
+            jobs,
+            visualize,
+        } => select_days(&days, &years).and_then(|days| {
+            let inputs = inputs(&input_dir, &examples_dir, &input, &days)?;
+            let answers_mode = match (verify, record) {
+                (true, _) => Some(AnswersMode::Verify),
+                (_, true) => Some(AnswersMode::Record),
+                _ => None,
+            };
+            let options = SolveOptions {
+                part,
+                timeout,
+                visualize,
+            };
+            run(
+                &days,
+                &inputs,
+                options,
+                format,
+                jobs.into(),
+                answers_mode.map(|mode| (mode, answers)),
+                &output,
+            )
+        }),
+        Command::Test { .. } if years != [DEFAULT_YEAR] => Err(format!(
+            "Only the days of {DEFAULT_YEAR} have tests in this crate"
+        )),
+        Command::Test { days } if days.is_empty() => test(&[]),
+        Command::Test { days } => select_days(&days, &years).and_then(|days| test(&days)),
+        Command::Bench {
+            days,
+            input,
+            runs,
+            warmup,
+            part,
+            timeout,
+            history,
+        } => select_days(&days, &years).and_then(|days| {
+            let inputs = inputs(&input_dir, &examples_dir, &input, &days)?;
+            let options = BenchOptions {
+                runs,
+                warmup,
+                part,
+                timeout,
+            };
+            bench(&days, &inputs, options, &history, &output)
+        }),
+        Command::Fetch { days, site, delay } => {
+            select_days(&days, &years).and_then(|days| fetch(&days, &input_dir, &site, delay))
+        }
+        Command::Submit {
+            day,
+            part,
+            site,
+            answers,
+            submissions,
+        } => single_year(&years).and_then(|year| {
+            let artifact_run = ArtifactRun::new(&output.out_dir);
+            let ctx = Context {
+                artifacts: artifact_run.day(year, day),
+                ..Context::default()
+            };
+            let solution = solve_part(year, day, part, &input_dir, &ctx);
+            clean_runs(&artifact_run, output.keep_runs);
+            submit(year, day, part, &solution?, &site, &answers, &submissions)
+        }),
+        Command::List => list(&years, &input_dir),
+        Command::Dashboard {
+            input,
+            answers,
+            timeout,
+        } => dashboard(
+            &years,
+            &input_dir,
+            &examples_dir,
+            &input,
+            &answers,
+            timeout,
+            &output,
+        ),
+        Command::Watch {
+            day,
+            input,
+            no_test,
+            interval,
+        } => single_year(&years).and_then(|year| {
+            if input.stdin {
+                return Err(String::from("The standard input cannot be watched"));
+            }
+            let inputs = Inputs::new(&input_dir, &examples_dir, input.source()?);
+            watch(year, day, &inputs, !no_test, interval, &output)
+        }),
+        Command::New { day, title } => {
+            single_year(&years).and_then(|year| new(year, day, title.as_deref(), &examples_dir))
+        }
+    }
+}
+
+/// Resolves the selectors in each year, the days are identified by their year and number
+fn select_days(selectors: &[DaySelector], years: &[u16]) -> Result<Vec<(u16, u8)>, String> {
+    let mut days = vec![];
+
+    for &year in years {
+        if registry::days(year).is_empty() {
+            return Err(format!("No day is registered for {year}"));
+        }
+
+        let selected = selector::resolve(selectors, |day| registry::is_solved(year, day));
+        days.extend(selected.into_iter().map(|day| (year, day)));
+    }
+
+    if days.is_empty() {
+        return Err(String::from("No day matches the given selectors"));
+    }
+
+    Ok(days)
+}
+
+/// The year of the commands working on a single day
+fn single_year(years: &[u16]) -> Result<u16, String> {
+    match years {
+        [year] => Ok(*year),
+        _ => Err(String::from("This command works on a single year")),
+    }
+}
+
+fn inputs(
+    input_dir: &Path,
+    examples_dir: &Path,
+    args: &InputArgs,
+    days: &[(u16, u8)],
+) -> Result<Inputs, String> {
+    let inputs = Inputs::new(input_dir, examples_dir, args.source()?);
+
+    if inputs.is_shared() && days.len() > 1 {
+        return Err(String::from(
+            "--input and --stdin can only be used with a single day",
+        ));
+    }
+
+    Ok(inputs)
+}
+
+/// How the days are solved
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SolveOptions {
+    /// Only solve one part
+    pub part: Option<Part>,
+    /// Stop a day that runs for longer
+    pub timeout: Option<Duration>,
+    pub visualize: Mode,
+}
+
+/// Reads the input of a day and solves it in its span, writing its files in the run. The report
+/// lists them, or tells why the day could not be solved
+pub fn solve_day(
+    year: u16,
+    day: u8,
+    inputs: &Inputs,
+    options: SolveOptions,
+    artifact_run: &ArtifactRun,
+) -> DayReport {
+    let _span = info_span!("day", year, day).entered();
+    let SolveOptions {
+        part,
+        timeout,
+        visualize,
+    } = options;
+    let solver = day_solver(year, day).and_then(|func| Ok((func, inputs.read(year, day)?)));
+    let (func, input) = match solver {
+        Ok(solver) => solver,
+        Err(e) => return DayReport::failed(year, day, None, e),
+    };
+    let part = registry::get(year, day).map_or(part, |info| info.parts_to_run(part));
+    let artifacts = artifact_run.day(year, day);
+    if let Err(e) = artifacts.clear() {
+        let e = format!("Could not remove the files of the previous solve: {e}");
+        return DayReport::failed(year, day, Some(&input), e);
+    }
+    let visualizer = match Visualizer::for_day(visualize, &artifacts) {
+        Ok(visualizer) => visualizer,
+        Err(e) => {
+            let e = format!("Could not create the visualization: {e}");
+            return DayReport::failed(year, day, Some(&input), e);
+        }
+    };
+
+    let ctx = Context {
+        cancel: CancelToken::new(),
+        visualizer,
+        artifacts: artifacts.clone(),
+    };
+    let day_report = match cancel::run_with_timeout(func, &input, part, timeout, &ctx) {
+        Ok(day_run) => DayReport::new(year, day, &input, &day_run),
+        Err(e) => DayReport {
+            timed_out: matches!(e, AocError::TimedOut(_)),
+            ..DayReport::failed(year, day, Some(&input), e)
+        },
+    };
+    // The visualization is flushed before its file is listed, the solver has released it unless
+    // it timed out
+    drop(ctx);
+
+    DayReport {
+        artifacts: artifacts.files(),
+        ..day_report
+    }
+}
+
+/// Removes the runs of the output directory that are not kept, a failure does not fail the
+/// command that produced the new run
+fn clean_runs(artifact_run: &ArtifactRun, retention: Retention) {
+    match artifact_run.clean(retention) {
+        Ok(removed) if !removed.is_empty() => debug!(removed = removed.len(), "old runs removed"),
+        Ok(_) => {}
+        Err(e) => warn!(
+            "Could not remove the old runs of {}: {e}",
+            artifact_run.out_dir.display()
+        ),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AnswersMode {
+    Verify,
+    Record,
+}
+
+fn run(
+    days: &[(u16, u8)],
+    inputs: &Inputs,
+    options: SolveOptions,
+    format: Format,
+    jobs: usize,
+    answers_mode: Option<(AnswersMode, PathBuf)>,
+    output: &OutputArgs,
+) -> Result<(), String> {
+    let mut report = Report::default();
+    let mut wrong_answers = 0;
+
+    if options.visualize == Mode::Terminal && jobs > 1 {
+        return Err(String::from(
+            "The days are visualized in the terminal one at a time, they cannot be solved with --jobs",
+        ));
+    }
+
+    // The frames would be mixed with the report on the standard output
+    if options.visualize == Mode::Terminal && format != Format::Text {
+        return Err(String::from(
+            "The days visualized in the terminal can only be reported as text, use --visualize file with --format json or csv",
+        ));
+    }
+
+    if answers_mode.is_some() && inputs.source != Source::Puzzle {
+        return Err(String::from(
+            "The stored answers are those of the puzzle inputs, they cannot be verified or recorded with another input",
+        ));
+    }
+
+    let mut answers = match &answers_mode {
+        Some((_, path)) => Answers::load(path)?,
+        None => Answers::default(),
+    };
+
+    let artifact_run = ArtifactRun::new(&output.out_dir);
+    let solve = |&(year, day): &(u16, u8)| solve_day(year, day, inputs, options, &artifact_run);
+
+    let time = Instant::now();
+    pool::run_ordered(days, jobs, solve, |mut day_report| {
+        match answers_mode {
+            Some((AnswersMode::Verify, _)) => wrong_answers += day_report.verify(&answers),
+            Some((AnswersMode::Record, _)) => day_report.record(&mut answers),
+            None => {}
+        }
+
+        if format == Format::Text {
+            day_report.print_text();
+        }
+
+        report.push(day_report);
+    });
+    report.wall_ms = report::as_ms(time.elapsed());
+    clean_runs(&artifact_run, output.keep_runs);
+
+    report.print(format)?;
+
+    if let Some((AnswersMode::Record, path)) = &answers_mode {
+        answers.save(path)?;
+    }
+
+    let mut problems = vec![];
+    if report.failures() > 0 {
+        problems.push(format!("{} day(s) could not be solved", report.failures()));
+    }
+    if report.timeouts() > 0 {
+        problems.push(format!("{} day(s) timed out", report.timeouts()));
+    }
+    if wrong_answers > 0 {
+        problems.push(format!(
+            "{wrong_answers} answer(s) do not match the stored ones"
+        ));
+    }
+
+    match problems.is_empty() {
+        true => Ok(()),
+        false => Err(problems.join(", ")),
+    }
+}
+
+fn test(days: &[(u16, u8)]) -> Result<(), String> {
+    let filters = days.iter().map(|(_, day)| format!("days::day{:02}::", day));
+
+    let status = process::Command::new("cargo")
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["test", "--"])
+        .args(filters)
+        .status()
+        .map_err(|e| format!("Could not run cargo test: {e}"))?;
+
+    if !status.success() {
+        return Err(String::from("Some tests failed"));
+    }
+
+    Ok(())
+}
+
+fn bench(
+    days: &[(u16, u8)],
+    inputs: &Inputs,
+    options: BenchOptions,
+    history_args: &HistoryArgs,
+    output: &OutputArgs,
+) -> Result<(), String> {
+    // Loaded before running so that this run is never its own baseline
+    let history = history::load(&history::default_path())?;
+
+    let mut benches = Vec::with_capacity(days.len());
+
+    let (mut failures, mut timeouts) = (0, 0);
+    let artifact_run = ArtifactRun::new(&output.out_dir);
+
+    for &(year, day) in days {
+        let solver = day_solver(year, day).and_then(|func| Ok((func, inputs.read(year, day)?)));
+        let (func, input) = match solver {
+            Ok(solver) => solver,
+            Err(e) => {
+                DayReport::failed(year, day, None, e).print_text();
+                failures += 1;
+                continue;
+            }
+        };
+
+        let _span = info_span!("day", year, day).entered();
+        let options = BenchOptions {
+            part: registry::get(year, day)
+                .map_or(options.part, |info| info.parts_to_run(options.part)),
+            ..options
+        };
+        // Every solve writes the same files again
+        let ctx = Context {
+            artifacts: artifact_run.day(year, day),
+            ..Context::default()
+        };
+        match bench::bench_day(year, day, func, &input, options, &ctx) {
+            Ok(day_bench) => {
+                day_bench.print();
+                if !day_bench.timed_out.is_empty() {
+                    timeouts += 1;
+                }
+                benches.push(day_bench);
+            }
+            Err(e) => {
+                let timed_out = matches!(e, AocError::TimedOut(_));
+                match timed_out {
+                    true => timeouts += 1,
+                    false => failures += 1,
+                }
+                DayReport {
+                    timed_out,
+                    ..DayReport::failed(year, day, Some(&input), e)
+                }
+                .print_text();
+            }
+        }
+    }
+
+    bench::print_summary(&benches);
+    clean_runs(&artifact_run, output.keep_runs);
+
+    let mut problems = vec![];
+    if failures > 0 {
+        problems.push(format!("{failures} day(s) could not be solved"));
+    }
+    if timeouts > 0 {
+        problems.push(format!(
+            "{timeouts} day(s) had parts that timed out after {:?}",
+            options.timeout
+        ));
+    }
+
+    // The history only tracks the puzzle inputs, and the days whose parts were all measured
+    if inputs.source == Source::Puzzle {
+        benches.retain(|day_bench| day_bench.timed_out.is_empty());
+        let regressions = update_history(&history, benches, options.part, history_args)?;
+        if regressions > 0 {
+            problems.push(format!(
+                "{regressions} day(s) are more than {}% slower than the baseline",
+                history_args.threshold
+            ));
+        }
+    }
+
+    match problems.is_empty() {
+        true => Ok(()),
+        false => Err(problems.join(", ")),
+    }
+}
+
+/// Compares the benchmarks with their baseline in the history, then saves them unless told
+/// otherwise. Returns the number of regressions
+fn update_history(
+    history: &[history::HistoryEntry],
+    benches: Vec<bench::DayBench>,
+    part: Option<Part>,
+    history_args: &HistoryArgs,
+) -> Result<usize, String> {
+    let comparisons: Vec<_> = benches
+        .iter()
+        .filter_map(|day_bench| {
+            let baseline = history::baseline(
+                history,
+                day_bench.year,
+                day_bench.day,
+                part,
+                history_args.baseline.as_deref(),
+            )?;
+            Some(history::Comparison::new(baseline, day_bench))
+        })
+        .collect();
+
+    let regressions = match comparisons.is_empty() {
+        true => {
+            println!("\nNo baseline to compare with");
+            0
+        }
+        false => history::print_comparisons(&comparisons, history_args.threshold),
+    };
+
+    if !history_args.no_save {
+        let revision = history::Revision::current();
+        let entries: Vec<_> = benches
+            .into_iter()
+            .map(|day_bench| history::HistoryEntry::new(&revision, part, day_bench))
+            .collect();
+        history::append(&history::default_path(), &entries)?;
+    }
+
+    Ok(regressions)
+}
+
+fn fetch(
+    days: &[(u16, u8)],
+    input_dir: &Path,
+    site: &SiteArgs,
+    delay: Duration,
+) -> Result<(), String> {
+    let session = client::session(&site.session_file)?;
+    let mut client = client::Client::new(&site.url, &session, delay);
+    let mut failures = 0;
+
+    for &(year, day) in days {
+        let path = input::puzzle_path(input_dir, year, day);
+
+        match client::fetch_input(&mut client, &path, year, day) {
+            Ok(Fetched::Downloaded(path)) => {
+                println!(
+                    "{} Day {:02} {} to {}",
+                    year,
+                    day,
+                    "downloaded".green(),
+                    path.display()
+                )
+            }
+            Ok(Fetched::Cached(path)) => {
+                println!(
+                    "{} Day {:02} {} at {}",
+                    year,
+                    day,
+                    "already downloaded".dimmed(),
+                    path.display()
+                )
+            }
+            Err(e) => {
+                println!("{} Day {:02} {}", year, day, e.red());
+                failures += 1;
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        _ => Err(format!("{failures} input(s) could not be downloaded")),
+    }
+}
+
+/// Solves one part of a day on its puzzle input to submit its answer, Ctrl-C cancels it
+fn solve_part(
+    year: u16,
+    day: u8,
+    part: Part,
+    input_dir: &Path,
+    ctx: &Context,
+) -> Result<Solution, String> {
+    submit::check_answer(year, day, part, &ctx.cancel)?;
+
+    let inputs = Inputs {
+        dir: input_dir.to_owned(),
+        examples_dir: input::default_examples_dir(),
+        source: Source::Puzzle,
+    };
+    let func = day_solver(year, day)?;
+    let input = inputs.read(year, day)?;
+    let _span = info_span!("day", year, day).entered();
+    let day_run = ctx
+        .cancel
+        .with_ctrlc(|| func(&input, Some(part), ctx))
+        .map_err(|e| format!("Day {:02} could not be solved: {e}", day))?;
+    // A solver stopped by Ctrl-C may still return what it had so far
+    submit::check_answer(year, day, part, &ctx.cancel)?;
+
+    Ok(match part {
+        Part::One => day_run.part1,
+        Part::Two => day_run.part2,
+    }
+    .expect("the requested part is always solved")
+    .solution)
+}
+
+fn submit(
+    year: u16,
+    day: u8,
+    part: Part,
+    solution: &Solution,
+    site: &SiteArgs,
+    answers_path: &Path,
+    submissions_path: &Path,
+) -> Result<(), String> {
+    let answer = solution.to_string();
+
+    println!("Day {:02} part {}: {}", day, part.number(), answer.bold());
+
+    let mut answers = Answers::load(answers_path)?;
+    match answers.get(year, day, part) {
+        Some(known) if known == answer => {
+            println!("{}", "Already accepted".dimmed());
+            return Ok(());
+        }
+        Some(known) => {
+            return Err(format!(
+                "The accepted answer is {known}, the solver must have changed"
+            ))
+        }
+        None => {}
+    }
+
+    let mut submissions = submit::Submissions::load(submissions_path)?;
+    let session = client::session(&site.session_file)?;
+    let mut client = client::Client::new(&site.url, &session, Duration::ZERO);
+
+    let outcome = submit::submit(
+        &mut client,
+        &mut submissions,
+        year,
+        day,
+        part,
+        &answer,
+        submit::now(),
+    )?;
+    submissions.save(submissions_path)?;
+
+    match outcome {
+        submit::Outcome::Correct => {
+            println!("{}", outcome.green());
+            answers.set(year, day, part, solution);
+            answers.save(answers_path)
+        }
+        outcome => Err(outcome.to_string()),
+    }
+}
+
+fn list(years: &[u16], input_dir: &Path) -> Result<(), String> {
+    for &year in years {
+        println!("{}", format!("=== {year} ===").bold());
+
+        let days = registry::days(year);
+        if days.is_empty() {
+            println!("{}", "No day is registered".dimmed());
+        }
+
+        for info in days {
+            let status = match info.status {
+                Status::Solved => "solved".green().to_string(),
+                Status::PartOne => "part 1".yellow().to_string(),
+                Status::Stub => "stub  ".yellow().to_string(),
+            };
+            let input = match input::puzzle_input(input_dir, year, info.day).is_file() {
+                true => "input".green().to_string(),
+                false => "no input".dimmed().to_string(),
+            };
+
+            println!(
+                "Day {:02} {:<24} {} {}",
+                info.day, info.title, status, input
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn new(year: u16, day: u8, title: Option<&str>, examples_dir: &Path) -> Result<(), String> {
+    if year != DEFAULT_YEAR {
+        return Err(format!(
+            "Only the days of {DEFAULT_YEAR} can be created in this crate"
+        ));
+    }
+
+    let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let title = title
+        .or_else(|| registry::get(year, day).map(|info| info.title))
+        .unwrap_or_default();
+
+    for path in scaffold::scaffold(&src_dir, examples_dir, year, day, title)? {
+        println!("{} {}", "Wrote".green(), path.display());
+    }
+    println!(
+        "Day {:02} is registered as a stub, remove `Stub` from its `register_day!` once solved",
+        day
+    );
+
+    Ok(())
+}
+
+/// A path that stays valid for the commands run from the crate directory
+fn absolute(path: &Path) -> Result<PathBuf, String> {
+    std::path::absolute(path).map_err(|e| format!("Could not resolve {}: {e}", path.display()))
+}
+
+/// The arguments of `run` that read the same inputs and write to the same output directory,
+/// when it is run by cargo from the crate directory
+fn run_args(inputs: &Inputs, output: &OutputArgs) -> Result<Vec<OsString>, String> {
+    let mut args = vec![
+        OsString::from("--input-dir"),
+        absolute(&inputs.dir)?.into(),
+        OsString::from("--examples-dir"),
+        absolute(&inputs.examples_dir)?.into(),
+        OsString::from("--out-dir"),
+        absolute(&output.out_dir)?.into(),
+        OsString::from("--keep-runs"),
+        output.keep_runs.to_string().into(),
+    ];
+    match &inputs.source {
+        Source::File(path) => args.extend([OsString::from("--input"), absolute(path)?.into()]),
+        Source::Example(n) => args.extend([OsString::from("--example"), n.to_string().into()]),
+        Source::Puzzle | Source::Stdin(_) => {}
+    }
+
+    Ok(args)
+}
+
+fn dashboard(
+    years: &[u16],
+    input_dir: &Path,
+    examples_dir: &Path,
+    args: &InputArgs,
+    answers_path: &Path,
+    timeout: Duration,
+    output: &OutputArgs,
+) -> Result<(), String> {
+    if args.stdin {
+        return Err(String::from(
+            "The standard input cannot be read by the dashboard",
+        ));
+    }
+
+    let inputs = Inputs::new(input_dir, examples_dir, args.source()?);
+    let run_args = run_args(&inputs, output)?;
+    let answers = Answers::load(answers_path)?;
+    let artifact_run = ArtifactRun::new(&output.out_dir);
+
+    let result = dashboard::Dashboard::new(
+        years,
+        answers,
+        inputs,
+        timeout,
+        run_args,
+        artifact_run.clone(),
+    )
+    .run();
+    clean_runs(&artifact_run, output.keep_runs);
+    result
+}
+
+fn watch(
+    year: u16,
+    day: u8,
+    inputs: &Inputs,
+    test: bool,
+    interval: Duration,
+    output: &OutputArgs,
+) -> Result<(), String> {
+    day_solver(year, day)?;
+
+    let examples_dir = absolute(&inputs.examples_dir)?;
+    let inputs = Inputs::new(
+        &absolute(&inputs.dir)?,
+        &examples_dir,
+        inputs.source.clone(),
+    );
+    let run_args = run_args(&inputs, output)?;
+
+    // The C solutions of the other years have no tests
+    let source = match year == DEFAULT_YEAR {
+        true => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/days")
+            .join(format!("day{:02}.rs", day)),
+        false => external::source_dir().join(format!("day{:02}", day)),
+    };
+    let mut paths = vec![source];
+    paths.extend(
+        inputs
+            .path(year, day)
+            .map(|path| absolute(&path))
+            .transpose()?,
+    );
+    paths.extend(
+        input::example_path(&examples_dir, year, day, 1)
+            .parent()
+            .map(Path::to_owned),
+    );
+    paths.dedup();
+
+    watch::Watch {
+        year,
+        day,
+        paths,
+        exe: env::current_exe().map_err(|e| format!("Could not find the runner: {e}"))?,
+        run_args,
+        test: test && year == DEFAULT_YEAR,
+        interval,
+    }
+    .run()
+}
+
+fn day_solver(year: u16, day: u8) -> Result<DayRunner, String> {
+    registry::get(year, day)
+        .map(|info| info.run)
+        .ok_or_else(|| format!("Day {:02} of {year} is not registered", day))
+}
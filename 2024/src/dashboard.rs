@@ -0,0 +1,716 @@
+use std::{
+    env,
+    ffi::OsString,
+    io::{self, Read, Write},
+    process::{Child, Command, Stdio},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+use advent_of_code::etc::artifacts::ArtifactRun;
+use advent_of_code::etc::registry::{self, DayInfo};
+use advent_of_code::etc::Part;
+use advent_of_code::input::{Inputs, Source};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use owo_colors::{OwoColorize, Style};
+
+use crate::answers::{Answers, Verdict};
+use crate::commands::SolveOptions;
+use crate::report::DayReport;
+
+const KEYS: &str = "↑↓ select · enter run · t test · v visualize · esc close · q quit";
+
+/// What is known about a day in this session
+#[derive(Debug)]
+struct Row {
+    info: &'static DayInfo,
+    report: Option<DayReport>,
+    running: bool,
+    /// Whether the tests passed the last time they ran
+    tests: Option<bool>,
+    testing: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Solved,
+    PartOne,
+    Stub,
+    Failing,
+    TimedOut,
+    Running,
+}
+
+impl Row {
+    fn status(&self) -> Status {
+        let wrong = |report: &DayReport| {
+            [&report.part1, &report.part2]
+                .into_iter()
+                .flatten()
+                .any(|part| matches!(part.verdict, Some(Verdict::Wrong { .. })))
+        };
+
+        match &self.report {
+            _ if self.running => Status::Running,
+            Some(report) if report.timed_out => Status::TimedOut,
+            Some(report) if report.error.is_some() || wrong(report) => Status::Failing,
+            _ if self.tests == Some(false) => Status::Failing,
+            _ if self.info.is_part_solved(Part::Two) => Status::Solved,
+            _ if self.info.is_solved() => Status::PartOne,
+            _ => Status::Stub,
+        }
+    }
+
+    /// The answer of the last run, or the stored one until the day runs
+    fn answer(&self, part: Part, answers: &Answers) -> (String, Style) {
+        let Some(report) = &self.report else {
+            let stored = answers.get(self.info.year, self.info.day, part);
+            return (stored.unwrap_or("-").to_owned(), Style::new().dimmed());
+        };
+
+        let part = match part {
+            Part::One => &report.part1,
+            Part::Two => &report.part2,
+        };
+        match part {
+            Some(part) => {
+                let style = match part.verdict {
+                    Some(Verdict::Wrong { .. }) => Style::new().red(),
+                    Some(Verdict::Correct) => Style::new().green(),
+                    _ => Style::new(),
+                };
+                (part.answer.to_string(), style)
+            }
+            None => (String::from("-"), Style::new().dimmed()),
+        }
+    }
+}
+
+/// What the lower pane shows
+#[derive(Debug)]
+enum Pane {
+    None,
+    Text {
+        title: String,
+        text: String,
+    },
+    /// The frames of a day visualized in the terminal, drawn as they arrive
+    Visualization {
+        title: String,
+        id: usize,
+        frame: String,
+        child: Child,
+    },
+}
+
+/// The results sent back by the work done in the background
+#[derive(Debug)]
+enum Message {
+    Solved(usize, Box<DayReport>),
+    Tested(usize, Result<(), String>),
+    Frame(usize, String),
+}
+
+/// Lists the registered days and runs them, their tests or their visualization on a keypress
+pub struct Dashboard {
+    rows: Vec<Row>,
+    selected: usize,
+    answers: Answers,
+    inputs: Inputs,
+    /// Stops the days that run for longer
+    timeout: Duration,
+    /// The arguments of `run` selecting the same inputs, for the visualizations
+    run_args: Vec<OsString>,
+    /// The run of this session, where the days write their files
+    artifact_run: ArtifactRun,
+    pane: Pane,
+    /// Tells apart the frames of the current visualization from the ones of a closed one
+    visualizations: usize,
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+}
+
+impl Dashboard {
+    pub fn new(
+        years: &[u16],
+        answers: Answers,
+        inputs: Inputs,
+        timeout: Duration,
+        run_args: Vec<OsString>,
+        artifact_run: ArtifactRun,
+    ) -> Self {
+        let rows = years
+            .iter()
+            .flat_map(|&year| registry::days(year))
+            .map(|info| Row {
+                info,
+                report: None,
+                running: false,
+                tests: None,
+                testing: false,
+            })
+            .collect();
+        let (sender, receiver) = mpsc::channel();
+
+        Dashboard {
+            rows,
+            selected: 0,
+            answers,
+            inputs,
+            timeout,
+            run_args,
+            artifact_run,
+            pane: Pane::None,
+            visualizations: 0,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Draws the dashboard until `q` is pressed
+    pub fn run(mut self) -> Result<(), String> {
+        if self.rows.is_empty() {
+            return Err(String::from("No day is registered"));
+        }
+
+        let result = Terminal::enter().and_then(|_terminal| self.event_loop());
+        self.close_pane();
+
+        result.map_err(|e| format!("The dashboard stopped: {e}"))
+    }
+
+    fn event_loop(&mut self) -> io::Result<()> {
+        loop {
+            while let Ok(message) = self.receiver.try_recv() {
+                self.receive(message);
+            }
+            self.draw()?;
+
+            if event::poll(Duration::from_millis(50))? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press && !self.press(key) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handles a key, returns `false` to quit
+    fn press(&mut self, key: KeyEvent) -> bool {
+        let last = self.rows.len() - 1;
+
+        match key.code {
+            KeyCode::Char('q') => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => self.selected = last,
+            KeyCode::Enter | KeyCode::Char('r') => self.solve(),
+            KeyCode::Char('t') => self.test(),
+            KeyCode::Char('v') => self.visualize(),
+            KeyCode::Esc => self.close_pane(),
+            _ => {}
+        }
+
+        true
+    }
+
+    fn receive(&mut self, message: Message) {
+        match message {
+            Message::Solved(idx, mut report) => {
+                // The stored answers are the ones of the puzzle inputs
+                if self.inputs.source == Source::Puzzle {
+                    report.verify(&self.answers);
+                }
+                let row = &mut self.rows[idx];
+                row.running = false;
+                let failure = report.error.clone().map(|error| Pane::Text {
+                    title: title(row.info, "failed"),
+                    text: error,
+                });
+                row.report = Some(*report);
+                if let Some(failure) = failure {
+                    self.show(failure);
+                }
+            }
+            Message::Tested(idx, result) => {
+                let row = &mut self.rows[idx];
+                row.testing = false;
+                row.tests = Some(result.is_ok());
+                if let Err(output) = result {
+                    let info = row.info;
+                    self.show(Pane::Text {
+                        title: title(info, "tests failed"),
+                        text: output,
+                    });
+                }
+            }
+            Message::Frame(id, text) => {
+                if let Pane::Visualization {
+                    id: current, frame, ..
+                } = &mut self.pane
+                {
+                    if *current == id {
+                        *frame = text;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Solves the selected day in the background
+    fn solve(&mut self) {
+        let idx = self.selected;
+        let row = &mut self.rows[idx];
+        if row.running {
+            return;
+        }
+        row.running = true;
+
+        let (year, day) = (row.info.year, row.info.day);
+        let (inputs, timeout, sender) = (self.inputs.clone(), self.timeout, self.sender.clone());
+        let artifact_run = self.artifact_run.clone();
+        thread::spawn(move || {
+            // A panic of the solver is already reported as a failure
+            let options = SolveOptions {
+                timeout: Some(timeout),
+                ..SolveOptions::default()
+            };
+            let report = crate::commands::solve_day(year, day, &inputs, options, &artifact_run);
+            let _ = sender.send(Message::Solved(idx, Box::new(report)));
+        });
+    }
+
+    /// Runs the tests of the selected day with cargo in the background
+    fn test(&mut self) {
+        let idx = self.selected;
+        let info = self.rows[idx].info;
+        if info.year != advent_of_code::DEFAULT_YEAR {
+            self.show(Pane::Text {
+                title: title(info, "tests"),
+                text: format!(
+                    "Only the days of {} have tests",
+                    advent_of_code::DEFAULT_YEAR
+                ),
+            });
+            return;
+        }
+
+        let row = &mut self.rows[idx];
+        if row.testing {
+            return;
+        }
+        row.testing = true;
+
+        let filter = format!("days::day{:02}::", row.info.day);
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let output = Command::new("cargo")
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .args(["test", "--quiet", "--", &filter])
+                .stdin(Stdio::null())
+                .output();
+            let result = match output {
+                Ok(output) if output.status.success() => Ok(()),
+                Ok(output) => Err(format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                )),
+                Err(e) => Err(format!("Could not run cargo test: {e}")),
+            };
+            let _ = sender.send(Message::Tested(idx, result));
+        });
+    }
+
+    /// Runs the selected day with its visualization in the terminal and shows its frames
+    fn visualize(&mut self) {
+        let info = self.rows[self.selected].info;
+        if info.year != advent_of_code::DEFAULT_YEAR {
+            self.show(Pane::Text {
+                title: title(info, "visualization"),
+                text: format!(
+                    "Only the days of {} have renderers",
+                    advent_of_code::DEFAULT_YEAR
+                ),
+            });
+            return;
+        }
+
+        // The dashboard itself runs the day, it has the same solvers
+        let child = env::current_exe().and_then(|exe| {
+            Command::new(exe)
+                .arg("run")
+                .arg(info.day.to_string())
+                .args(["--year", &info.year.to_string()])
+                .args(["--visualize", "terminal"])
+                .args(&self.run_args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+        });
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                self.show(Pane::Text {
+                    title: title(info, "visualization"),
+                    text: format!("Could not run the day: {e}"),
+                });
+                return;
+            }
+        };
+
+        self.visualizations += 1;
+        let id = self.visualizations;
+        let mut stdout = child.stdout.take().expect("the output is piped");
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let mut frames = Frames::default();
+            let mut buffer = [0; 4096];
+            while let Ok(n @ 1..) = stdout.read(&mut buffer) {
+                if let Some(frame) = frames.push(&buffer[..n]) {
+                    if sender.send(Message::Frame(id, frame)).is_err() {
+                        return;
+                    }
+                }
+            }
+            let _ = sender.send(Message::Frame(id, frames.finish()));
+        });
+
+        self.show(Pane::Visualization {
+            title: title(info, "visualization"),
+            id,
+            frame: String::from("Building…"),
+            child,
+        });
+    }
+
+    /// Replaces the pane, stopping the visualization it showed
+    fn show(&mut self, pane: Pane) {
+        self.close_pane();
+        self.pane = pane;
+    }
+
+    fn close_pane(&mut self) {
+        if let Pane::Visualization { child, .. } = &mut self.pane {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        self.pane = Pane::None;
+    }
+
+    fn draw(&self) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+
+        let mut lines = vec![
+            Line::new(width).cell(&format!(" Advent of Code  {KEYS}"), Style::new().bold()),
+            Line::new(width).cell(
+                &format!(
+                    "  {:<4} {:<3} {:<24} {:<9} {:<7} {:<16} {:<16} {:>11}",
+                    "Year", "Day", "Title", "Status", "Tests", "Part 1", "Part 2", "Time"
+                ),
+                Style::new().dimmed(),
+            ),
+        ];
+
+        let pane_height = match self.pane {
+            Pane::None => 0,
+            _ => height.saturating_sub(lines.len()) / 2,
+        };
+        let table_height = height.saturating_sub(lines.len() + pane_height).max(1);
+        // Scrolls so that the selected day is always visible
+        let first = (self.selected + 1).saturating_sub(table_height);
+
+        for (idx, row) in self.rows.iter().enumerate().skip(first).take(table_height) {
+            lines.push(self.row_line(row, idx == self.selected, width));
+        }
+        lines.resize_with(height.saturating_sub(pane_height), || Line::new(width));
+
+        match &self.pane {
+            Pane::None => {}
+            Pane::Text { title, text }
+            | Pane::Visualization {
+                title, frame: text, ..
+            } => {
+                lines.push(Line::new(width).cell(&format!("─── {title} "), Style::new().bold()));
+                let text = strip_ansi(text);
+                for text in text.lines().take(pane_height.saturating_sub(1)) {
+                    lines.push(Line::new(width).cell(text, Style::new()));
+                }
+            }
+        }
+        lines.resize_with(height, || Line::new(width));
+
+        let mut stdout = io::stdout();
+        for (y, line) in lines.iter().enumerate() {
+            queue!(
+                stdout,
+                cursor::MoveTo(0, y as u16),
+                Print(&line.text),
+                terminal::Clear(ClearType::UntilNewLine)
+            )?;
+        }
+        stdout.flush()
+    }
+
+    fn row_line(&self, row: &Row, selected: bool, width: usize) -> Line {
+        let (status, status_style) = match row.status() {
+            Status::Solved => ("solved", Style::new().green()),
+            Status::PartOne => ("part 1", Style::new().yellow()),
+            Status::Stub => ("stub", Style::new().yellow()),
+            Status::Failing => ("failing", Style::new().red()),
+            Status::TimedOut => ("timed out", Style::new().yellow()),
+            Status::Running => ("running…", Style::new().cyan()),
+        };
+        let (tests, tests_style) = match (row.testing, row.tests) {
+            (true, _) => ("…", Style::new().cyan()),
+            (false, Some(true)) => ("passed", Style::new().green()),
+            (false, Some(false)) => ("failed", Style::new().red()),
+            (false, None) => ("-", Style::new().dimmed()),
+        };
+        let (part1, part1_style) = row.answer(Part::One, &self.answers);
+        let (part2, part2_style) = row.answer(Part::Two, &self.answers);
+        let time = match &row.report {
+            Some(report) if report.error.is_none() => format!("{:.4} ms", report.total_ms),
+            _ => String::from("-"),
+        };
+
+        let marker = match selected {
+            true => Style::new().bold(),
+            false => Style::new(),
+        };
+        Line::new(width)
+            .cell(if selected { "› " } else { "  " }, marker)
+            .cell(
+                &format!("{:<4} {:>3} ", row.info.year, row.info.day),
+                marker,
+            )
+            .cell(&fit(row.info.title, 24), marker)
+            .cell(" ", Style::new())
+            .cell(&fit(status, 9), status_style)
+            .cell(" ", Style::new())
+            .cell(&fit(tests, 7), tests_style)
+            .cell(" ", Style::new())
+            .cell(&fit(&part1, 16), part1_style)
+            .cell(" ", Style::new())
+            .cell(&fit(&part2, 16), part2_style)
+            .cell(&format!(" {time:>11}"), Style::new())
+    }
+}
+
+/// A line of the screen, its styled cells are dropped once the line is full so that it never
+/// wraps
+struct Line {
+    text: String,
+    room: usize,
+}
+
+impl Line {
+    fn new(width: usize) -> Self {
+        Line {
+            text: String::new(),
+            room: width,
+        }
+    }
+
+    fn cell(mut self, text: &str, style: Style) -> Self {
+        let text = fit_max(text, self.room);
+        self.room -= text.chars().count();
+        self.text += &text.style(style).to_string();
+        self
+    }
+}
+
+/// Pads or truncates a text to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    format!("{:<width$}", fit_max(text, width))
+}
+
+/// Truncates a text to at most `width` characters, with an ellipsis if anything is cut
+fn fit_max(text: &str, width: usize) -> String {
+    match text.chars().count() <= width {
+        true => text.to_owned(),
+        false if width == 0 => String::new(),
+        false => text.chars().take(width - 1).chain(['…']).collect(),
+    }
+}
+
+fn title(info: &DayInfo, what: &str) -> String {
+    format!("{} day {:02} {what}", info.year, info.day)
+}
+
+/// Removes the escape sequences of the terminal, which would move the cursor out of the pane
+fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                // A control sequence ends with a letter or another final byte
+                if chars.next() == Some('[') {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+            '\r' => {}
+            c => stripped.push(c),
+        }
+    }
+
+    stripped
+}
+
+/// Splits the output of the renderers in frames, each of them starts by clearing the screen
+#[derive(Debug, Default)]
+struct Frames {
+    pending: Vec<u8>,
+}
+
+impl Frames {
+    const CLEAR: &'static [u8] = b"\x1b[2J";
+
+    /// Adds some output and returns the latest frame it completed, if any
+    fn push(&mut self, bytes: &[u8]) -> Option<String> {
+        self.pending.extend_from_slice(bytes);
+
+        let last_clear = rfind(&self.pending, Self::CLEAR)?;
+        let complete = &self.pending[..last_clear];
+        let start = rfind(complete, Self::CLEAR).map_or(0, |idx| idx + Self::CLEAR.len());
+        let frame = String::from_utf8_lossy(&complete[start..]).into_owned();
+        self.pending.drain(..last_clear + Self::CLEAR.len());
+
+        (!frame.trim().is_empty()).then_some(frame)
+    }
+
+    /// The output after the last clear, with the answers once the day is solved
+    fn finish(self) -> String {
+        String::from_utf8_lossy(&self.pending).into_owned()
+    }
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
+
+/// The terminal in raw mode on the alternate screen, restored when dropped even on a panic
+struct Terminal;
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Terminal)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use advent_of_code::etc::AocError;
+
+    fn row(day: u8) -> Row {
+        Row {
+            info: registry::get(2024, day).unwrap(),
+            report: None,
+            running: false,
+            tests: None,
+            testing: false,
+        }
+    }
+
+    #[test]
+    fn statuses() {
+        assert_eq!(row(1).status(), Status::Solved);
+        assert_eq!(row(16).status(), Status::PartOne);
+        assert_eq!(row(25).status(), Status::Stub);
+
+        let mut failing = row(1);
+        failing.report = Some(DayReport::failed(
+            2024,
+            1,
+            None,
+            AocError::invalid_input("empty"),
+        ));
+        assert_eq!(failing.status(), Status::Failing);
+        failing.running = true;
+        assert_eq!(failing.status(), Status::Running);
+
+        let mut timed_out = row(1);
+        timed_out.report = Some(DayReport {
+            timed_out: true,
+            ..DayReport::failed(2024, 1, None, AocError::TimedOut(Duration::from_secs(1)))
+        });
+        assert_eq!(timed_out.status(), Status::TimedOut);
+
+        let mut untested = row(2);
+        untested.tests = Some(false);
+        assert_eq!(untested.status(), Status::Failing);
+    }
+
+    #[test]
+    fn stored_answers() {
+        let mut answers = Answers::default();
+        answers.set(
+            2024,
+            1,
+            Part::One,
+            &advent_of_code::etc::Solution::from(11_u64),
+        );
+
+        assert_eq!(row(1).answer(Part::One, &answers).0, "11");
+        assert_eq!(row(1).answer(Part::Two, &answers).0, "-");
+    }
+
+    #[test]
+    fn split_frames() {
+        let mut frames = Frames::default();
+        assert_eq!(frames.push(b"\x1b[2Jfirst"), None);
+        assert_eq!(
+            frames.push(b" frame\n\x1b[2Jsec"),
+            Some(String::from("first frame\n"))
+        );
+        assert_eq!(
+            frames.push(b"ond\n\x1b[2Jthird\n\x1b[2Jlast"),
+            Some(String::from("third\n"))
+        );
+        assert_eq!(frames.finish(), "last");
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(strip_ansi("\x1b[31mred\x1b[0m\r\n"), "red\n");
+        assert_eq!(fit("Historian Hysteria", 5), "Hist…");
+        assert_eq!(fit("Day", 5), "Day  ");
+        assert_eq!(fit_max("Day", 0), "");
+
+        let line = Line::new(6)
+            .cell("abcd", Style::new())
+            .cell("efgh", Style::new());
+        assert_eq!(line.room, 0);
+        assert_eq!(strip_ansi(&line.text), "abcde…");
+    }
+}
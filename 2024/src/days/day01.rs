@@ -1,59 +1,63 @@
 use std::collections::HashMap;
 
-use crate::{Solution, SolutionPair};
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    //////////
-    // Part 1
-    //////////
-    let res: Vec<&str> = input.split_whitespace().collect();
-
-    let mut left_list: Vec<u64> = vec![];
-    let mut right_list: Vec<u64> = vec![];
-
-    res.windows(2).step_by(2).for_each(|w| {
-        left_list.push(
-            w[0].parse()
-                .unwrap_or_else(|e| panic!("The input is not formed correctly: {e}")),
-        );
-        right_list.push(
-            w[1].parse()
-                .unwrap_or_else(|e| panic!("The input is not formed correctly: {e}")),
-        );
-    });
-
-    left_list.sort();
-    right_list.sort();
-
-    let sol1: u64 = left_list
-        .iter()
-        .zip(right_list.iter())
-        .fold(0_u64, |acc, (l, r)| acc + l.abs_diff(*r));
-
-    //////////
-    // Part 2
-    //////////
-    let mut left_map: HashMap<u64, usize> = HashMap::new();
-    for num in left_list {
-        let count = left_map.entry(num).or_insert(0);
-        *count += 1;
-    }
+pub struct Day01;
 
-    let mut right_map: HashMap<u64, usize> = HashMap::new();
-    for num in right_list {
-        let count = right_map.entry(num).or_insert(0);
-        *count += 1;
-    }
+register_day!(Day01, 2024, 1, "Historian Hysteria");
+
+impl Day for Day01 {
+    /// The left and right lists, sorted
+    type Parsed = (Vec<u64>, Vec<u64>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        let res: Vec<&str> = input.split_whitespace().collect();
+
+        let mut left_list: Vec<u64> = vec![];
+        let mut right_list: Vec<u64> = vec![];
 
-    let mut sol2: u64 = 0;
-    for (key,occurency) in left_map {
-        sol2 += key * occurency as u64 * *right_map.get(&key).unwrap_or(&0) as u64;
+        for w in res.windows(2).step_by(2) {
+            left_list.push(w[0].parse()?);
+            right_list.push(w[1].parse()?);
+        }
+
+        left_list.sort();
+        right_list.sort();
+
+        Ok((left_list, right_list))
     }
 
+    fn part1((left_list, right_list): &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let sol1: u64 = left_list
+            .iter()
+            .zip(right_list.iter())
+            .fold(0_u64, |acc, (l, r)| acc + l.abs_diff(*r));
 
-    (Solution::from(sol1), Solution::from(sol2))
+        Ok(Solution::from(sol1))
+    }
+
+    fn part2((left_list, right_list): &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut left_map: HashMap<u64, usize> = HashMap::new();
+        for &num in left_list {
+            let count = left_map.entry(num).or_insert(0);
+            *count += 1;
+        }
+
+        let mut right_map: HashMap<u64, usize> = HashMap::new();
+        for &num in right_list {
+            let count = right_map.entry(num).or_insert(0);
+            *count += 1;
+        }
+
+        let mut sol2: u64 = 0;
+        for (key, occurency) in left_map {
+            sol2 += key * occurency as u64 * *right_map.get(&key).unwrap_or(&0) as u64;
+        }
+
+        Ok(Solution::from(sol2))
+    }
 }
 
 #[cfg(test)]
@@ -62,7 +66,7 @@ mod test {
 
     #[test]
     fn empty_input() {
-        let (p1, p2) = solve("");
+        let (p1, p2) = Day01::solve("").unwrap();
         assert_eq!(p1, Solution::from(0_u64));
         assert_eq!(p2, Solution::from(0_u64));
     }
@@ -73,7 +77,7 @@ mod test {
          1  1
          2  2
          3  3";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day01::solve(input).unwrap();
         assert_eq!(p1, Solution::from(0_u64));
         assert_eq!(p2, Solution::from(6_u64));
     }
@@ -84,7 +88,7 @@ mod test {
          1  1
          2  2
          3  4";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day01::solve(input).unwrap();
         assert_eq!(p1, Solution::from(1_u64));
         assert_eq!(p2, Solution::from(3_u64));
     }
@@ -95,7 +99,7 @@ mod test {
          3  3
          2  2
          1  1";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day01::solve(input).unwrap();
         assert_eq!(p1, Solution::from(0_u64));
         assert_eq!(p2, Solution::from(10_u64));
     }
@@ -108,7 +112,7 @@ mod test {
          1   3
          3   9
          3   3";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day01::solve(input).unwrap();
         assert_eq!(p1, Solution::from(11_u64));
         assert_eq!(p2, Solution::from(31_u64));
     }
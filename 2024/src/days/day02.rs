@@ -1,46 +1,61 @@
-use crate::{Solution, SolutionPair};
+use tracing::trace;
+
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    if input.is_empty() {
-        return (0_u64.into(), 0_u64.into());
+pub struct Day02;
+
+register_day!(Day02, 2024, 2, "Red-Nosed Reports");
+
+impl Day for Day02 {
+    type Parsed = Vec<Vec<u64>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        if input.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(input
+            .split('\n')
+            .map(|report| {
+                report
+                    .split_whitespace()
+                    .filter_map(|level| level.trim().parse().ok())
+                    .collect()
+            })
+            .collect())
     }
 
-    let mut sol1: u64 = 0;
-    let mut sol2: u64 = 0;
-
-    let reports: Vec<Vec<u64>> = input
-        .split('\n')
-        .map(|report| {
-            report
-                .split_whitespace()
-                .filter_map(|level| level.trim().parse().ok())
-                .collect()
-        })
-        .collect();
-
-    for report in reports.iter() {
-        match is_report_safe(report) {
-            true => {
-                sol1 += 1;
-                sol2 += 1
-            }
-            false => {
-                for (removed_idx, _) in report.iter().enumerate() {
-                    let mut report_removed = report.clone();
-                    report_removed.remove(removed_idx);
-
-                    if is_report_safe(&report_removed) {
-                        sol2 += 1;
-                        break;
+    fn part1(reports: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let sol1: u64 = reports.iter().filter(|r| is_report_safe(r)).count() as u64;
+
+        Ok(Solution::from(sol1))
+    }
+
+    fn part2(reports: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut sol2: u64 = 0;
+
+        for report in reports.iter() {
+            match is_report_safe(report) {
+                true => sol2 += 1,
+                false => {
+                    for (removed_idx, _) in report.iter().enumerate() {
+                        let mut report_removed = report.clone();
+                        report_removed.remove(removed_idx);
+
+                        if is_report_safe(&report_removed) {
+                            trace!(?report, removed_idx, "report safe without a level");
+                            sol2 += 1;
+                            break;
+                        }
                     }
                 }
             }
         }
-    }
 
-    (Solution::from(sol1), Solution::from(sol2))
+        Ok(Solution::from(sol2))
+    }
 }
 
 fn is_report_safe(report: &[u64]) -> bool {
@@ -77,7 +92,7 @@ mod test {
 
     #[test]
     fn empty_input() {
-        let (p1, p2) = solve("");
+        let (p1, p2) = Day02::solve("").unwrap();
         assert_eq!(p1, Solution::from(0_u64));
         assert_eq!(p2, Solution::from(0_u64));
     }
@@ -91,7 +106,7 @@ mod test {
          1 2 1
          1 2 7 8 9
          1 3 2 4 5";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day02::solve(input).unwrap();
         assert_eq!(p1, Solution::from(4_u64));
         assert_eq!(p2, Solution::from(6_u64));
     }
@@ -105,7 +120,7 @@ mod test {
          4 3 4 2 1
          9 7 6 2 1
          8 6 4 4 1";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day02::solve(input).unwrap();
         assert_eq!(p1, Solution::from(4_u64));
         assert_eq!(p2, Solution::from(6_u64));
     }
@@ -114,7 +129,7 @@ mod test {
     fn all_zeros() {
         let input = "0 0 0 0";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day02::solve(input).unwrap();
         assert_eq!(p1, Solution::from(0_u64));
         assert_eq!(p2, Solution::from(0_u64));
     }
@@ -128,7 +143,7 @@ mod test {
             8 6 4 4 1
             1 3 6 7 9";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day02::solve(input).unwrap();
         assert_eq!(p1, Solution::from(2_u64));
         assert_eq!(p2, Solution::from(4_u64));
     }
@@ -137,7 +152,7 @@ mod test {
     fn one_level() {
         let input = "2";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day02::solve(input).unwrap();
         assert_eq!(p1, Solution::from(1_u64));
         assert_eq!(p2, Solution::from(1_u64));
     }
@@ -146,7 +161,7 @@ mod test {
     fn combination() {
         let input = "1 9 9 2 3 9 4";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day02::solve(input).unwrap();
         assert_eq!(p1, Solution::from(0_u64));
         assert_eq!(p2, Solution::from(0_u64));
     }
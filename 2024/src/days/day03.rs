@@ -1,36 +1,49 @@
-use crate::{Solution, SolutionPair};
+use crate::{register_day, AocError, Context, Day, Solution};
 use regex::Regex;
+use tracing::trace;
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    //////////
-    // Part 1
-    //////////
+pub struct Day03;
 
-    let mul_regex = Regex::new(r"mul\(([0-9]+),([0-9]+)\)").unwrap();
-    let mut sol1: u64 = 0;
+register_day!(Day03, 2024, 3, "Mull It Over");
 
-    for (_, [fac1, fac2]) in mul_regex.captures_iter(input).map(|c| c.extract()) {
-        sol1 += fac1.parse().unwrap_or(1) * fac2.parse().unwrap_or(1);
+impl Day for Day03 {
+    /// The corrupted memory is scanned as is
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        Ok(input.to_owned())
     }
 
-    //////////
-    // Part 2
-    //////////
+    fn part1(input: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mul_regex = Regex::new(r"mul\(([0-9]+),([0-9]+)\)").unwrap();
+        let mut sol1: u64 = 0;
+
+        for (_, [fac1, fac2]) in mul_regex.captures_iter(input).map(|c| c.extract()) {
+            trace!(fac1, fac2, "multiplication");
+            sol1 += fac1.parse().unwrap_or(1) * fac2.parse().unwrap_or(1);
+        }
 
-    let mut sol2: u64 = 0;
+        Ok(Solution::from(sol1))
+    }
 
-    let do_dont_regex = Regex::new(r"(do\(\))([\s\S]*?)(don't\(\))").unwrap();
-    let do_input = format!("do(){}don't()", input);
+    fn part2(input: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mul_regex = Regex::new(r"mul\(([0-9]+),([0-9]+)\)").unwrap();
+        let mut sol2: u64 = 0;
 
-    for (_, [_, to_do, _]) in do_dont_regex.captures_iter(&do_input).map(|c| c.extract()) {
-        for (_, [fac1, fac2]) in mul_regex.captures_iter(to_do).map(|c| c.extract()) {
-            sol2 += fac1.parse().unwrap_or(1) * fac2.parse().unwrap_or(1);
+        let do_dont_regex = Regex::new(r"(do\(\))([\s\S]*?)(don't\(\))").unwrap();
+        let do_input = format!("do(){}don't()", input);
+
+        for (_, [_, to_do, _]) in do_dont_regex.captures_iter(&do_input).map(|c| c.extract()) {
+            for (_, [fac1, fac2]) in mul_regex.captures_iter(to_do).map(|c| c.extract()) {
+                trace!(fac1, fac2, "enabled multiplication");
+                sol2 += fac1.parse().unwrap_or(1) * fac2.parse().unwrap_or(1);
+            }
         }
-    }
 
-    (Solution::from(sol1), Solution::from(sol2))
+        Ok(Solution::from(sol2))
+    }
 }
 
 #[cfg(test)]
@@ -39,7 +52,7 @@ mod test {
 
     #[test]
     fn empty_input() {
-        let (p1, p2) = solve("");
+        let (p1, p2) = Day03::solve("").unwrap();
         assert_eq!(p1, Solution::from(0_u64));
         assert_eq!(p2, Solution::from(0_u64));
     }
@@ -47,7 +60,7 @@ mod test {
     #[test]
     fn single_mul() {
         let input = "do()mul(2,2)don't()mul(1,1)gibebrishmul(3,4)";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day03::solve(input).unwrap();
         assert_eq!(p1, Solution::from(17_u64));
         assert_eq!(p2, Solution::from(4_u64));
     }
@@ -55,7 +68,7 @@ mod test {
     #[test]
     fn wrong_muls() {
         let input = "mal(2,2)mul(2, 2)mul( 2,2)mul( 2 , 2 )mul(2 2)";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day03::solve(input).unwrap();
         assert_eq!(p1, Solution::from(0_u64));
         assert_eq!(p2, Solution::from(0_u64));
     }
@@ -63,7 +76,7 @@ mod test {
     #[test]
     fn do_dont() {
         let input = "don't()mul(1,1)mul(1,1)mul(1,1)do()mul(1,1)";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day03::solve(input).unwrap();
         assert_eq!(p1, Solution::from(4_u64));
         assert_eq!(p2, Solution::from(1_u64));
     }
@@ -71,11 +84,11 @@ mod test {
     #[test]
     fn aoc_test() {
         let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        let (p1, _) = solve(input);
+        let (p1, _) = Day03::solve(input).unwrap();
         assert_eq!(p1, Solution::from(161_u64));
 
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        let (_, p2) = solve(input);
+        let (_, p2) = Day03::solve(input).unwrap();
         assert_eq!(p2, Solution::from(48_u64));
     }
 }
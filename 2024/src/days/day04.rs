@@ -1,23 +1,59 @@
-use crate::{Solution, SolutionPair};
+use tracing::trace;
+
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let word_search: Vec<Vec<char>> = input.lines().map(|l| l.trim().chars().collect()).collect();
-
-    let mut sol1: u64 = 0;
-    let mut sol2: u64 = 0;
-    for (row, line) in word_search.iter().enumerate() {
-        for (col, char) in line.iter().enumerate() {
-            if char == &'X' {
-                sol1 += count_xmas((col, row), &word_search);
-            } else if char == &'A' {
-                sol2 += count_x_mas((col, row), &word_search);
+pub struct Day04;
+
+register_day!(Day04, 2024, 4, "Ceres Search");
+
+impl Day for Day04 {
+    type Parsed = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        Ok(input.lines().map(|l| l.trim().chars().collect()).collect())
+    }
+
+    fn part1(word_search: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut sol1: u64 = 0;
+        for (row, line) in word_search.iter().enumerate() {
+            for (col, char) in line.iter().enumerate() {
+                if char == &'X' {
+                    let count = count_xmas((col, row), word_search);
+                    if count > 0 {
+                        trace!(x = col, y = row, count, "XMAS from the X");
+                    }
+                    sol1 += count;
+                }
             }
         }
+
+        Ok(Solution::from(sol1))
     }
 
-    (Solution::from(sol1), Solution::from(sol2))
+    fn part2(word_search: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut sol2: u64 = 0;
+        for (row, line) in word_search.iter().enumerate() {
+            for (col, char) in line.iter().enumerate() {
+                if char == &'A' {
+                    let count = count_x_mas((col, row), word_search);
+                    if count > 0 {
+                        trace!(x = col, y = row, "X-MAS around the A");
+                    }
+                    sol2 += count;
+                }
+            }
+        }
+
+        Ok(Solution::from(sol2))
+    }
+}
+
+/// The character `dx` columns and `dy` rows away from `(x, y)`, `None` outside of the matrix
+fn char_at(matrix: &[Vec<char>], (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<char> {
+    let line = matrix.get(y.checked_add_signed(dy)?)?;
+    line.get(x.checked_add_signed(dx)?).copied()
 }
 
 /// Checks the following pattern with an X in the middle and counts how many times XMAS or SAMX
@@ -34,40 +70,14 @@ pub fn solve(input: &str) -> SolutionPair {
 fn count_xmas((x, y): (usize, usize), matrix: &[Vec<char>]) -> u64 {
     let mut xmas_count = 0;
     let checks = &["XMAS", "SAMX"];
+    let line = |step: fn(isize) -> (isize, isize)| -> String {
+        (-3..=3).filter_map(|i| char_at(matrix, (x, y), step(i))).collect()
+    };
 
-    let horizontal: String = (0..7)
-        .map(|col| match matrix.get(y).unwrap().get((x - 3) + col) {
-            Some(c) => c.to_string(),
-            None => String::default(),
-        })
-        .collect();
-
-    let vertical: String = (0..7)
-        .map(|row| match matrix.get((y - 3) + row) {
-            Some(str) => str.get(x).unwrap().to_string(),
-            None => String::default(),
-        })
-        .collect();
-
-    let diagonal_r: String = (0..7)
-        .map(|row| match matrix.get((y - 3) + row) {
-            Some(str) => match str.get((x - 3) + row) {
-                Some(c) => c.to_string(),
-                None => String::default(),
-            },
-            None => String::default(),
-        })
-        .collect();
-
-    let diagonal_l: String = (0..7)
-        .map(|row| match matrix.get((y - 3) + row) {
-            Some(str) => match str.get((x + 3) - row) {
-                Some(c) => c.to_string(),
-                None => String::default(),
-            },
-            None => String::default(),
-        })
-        .collect();
+    let horizontal = line(|i| (i, 0));
+    let vertical = line(|i| (0, i));
+    let diagonal_r = line(|i| (i, i));
+    let diagonal_l = line(|i| (-i, i));
 
     for check in checks {
         if horizontal.contains(check) {
@@ -90,26 +100,12 @@ fn count_xmas((x, y): (usize, usize), matrix: &[Vec<char>]) -> u64 {
 fn count_x_mas((x, y): (usize, usize), matrix: &[Vec<char>]) -> u64 {
     let mut xmas_count = 0;
     let checks = &["MAS", "SAM"];
+    let line = |step: fn(isize) -> (isize, isize)| -> String {
+        (-1..=1).filter_map(|i| char_at(matrix, (x, y), step(i))).collect()
+    };
 
-    let diagonal_r: String = (0..3)
-        .map(|row| match matrix.get((y - 1) + row) {
-            Some(str) => match str.get((x - 1) + row) {
-                Some(c) => c.to_string(),
-                None => String::default(),
-            },
-            None => String::default(),
-        })
-        .collect();
-
-    let diagonal_l: String = (0..3)
-        .map(|row| match matrix.get((y - 1) + row) {
-            Some(str) => match str.get((x + 1) - row) {
-                Some(c) => c.to_string(),
-                None => String::default(),
-            },
-            None => String::default(),
-        })
-        .collect();
+    let diagonal_r = line(|i| (i, i));
+    let diagonal_l = line(|i| (-i, i));
 
     if (diagonal_r.contains(checks[0])
         || diagonal_r.contains(checks[1])) && (diagonal_l.contains(checks[0])
@@ -183,10 +179,19 @@ mod test {
             ..........
             ";
 
-        let (_, p2) = solve(input);
+        let (_, p2) = Day04::solve(input).unwrap();
         assert_eq!(p2, Solution::from(9_u64));
     }
 
+    #[test]
+    fn x_on_the_edges() {
+        let (p1, _) = Day04::solve("XMAS").unwrap();
+        assert_eq!(p1, Solution::from(1_u64));
+
+        // The lines are not all as long, which must not read past the end of the shorter ones
+        assert!(Day04::solve("XMAS\nX\nSAMXMAS").is_ok());
+    }
+
     #[test]
     fn edge() {
         let input = "
@@ -195,7 +200,7 @@ mod test {
             S.S
             ";
 
-        let (_, p2) = solve(input);
+        let (_, p2) = Day04::solve(input).unwrap();
         assert_eq!(p2, Solution::from(0_u64));
     }
 }
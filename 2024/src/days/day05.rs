@@ -1,26 +1,50 @@
 use std::collections::HashMap;
 
-use crate::{Solution, SolutionPair};
+use tracing::trace;
+
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (rules, updates): (HashMap<u64, Vec<u64>>, Vec<Vec<u64>>) = parse_input(input);
+pub struct Day05;
 
-    let mut sol1: u64 = 0;
-    let mut sol2: u64 = 0;
+register_day!(Day05, 2024, 5, "Print Queue");
 
-    for mut update in updates {
-        let ordered = is_ordered(&update, &rules);
-        if ordered {
-            sol1 += *update.get(update.len() / 2).unwrap_or(&0);
-        } else {
-            fix_unordered(&mut update, &rules);
-            sol2 += *update.get(update.len() / 2).unwrap_or(&0);
+impl Day for Day05 {
+    /// The pages that must come after each page and the updates
+    type Parsed = (HashMap<u64, Vec<u64>>, Vec<Vec<u64>>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        Ok(parse_input(input))
+    }
+
+    fn part1((rules, updates): &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut sol1: u64 = 0;
+
+        for update in updates {
+            if is_ordered(update, rules) {
+                trace!(?update, "update in the right order");
+                sol1 += *update.get(update.len() / 2).unwrap_or(&0);
+            }
         }
+
+        Ok(Solution::from(sol1))
     }
 
-    (Solution::from(sol1), Solution::from(sol2))
+    fn part2((rules, updates): &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut sol2: u64 = 0;
+
+        for update in updates {
+            if !is_ordered(update, rules) {
+                let mut update = update.clone();
+                fix_unordered(&mut update, rules);
+                trace!(?update, "update ordered");
+                sol2 += *update.get(update.len() / 2).unwrap_or(&0);
+            }
+        }
+
+        Ok(Solution::from(sol2))
+    }
 }
 
 fn is_ordered(update: &[u64], rules: &HashMap<u64, Vec<u64>>) -> bool {
@@ -38,22 +62,24 @@ fn is_ordered(update: &[u64], rules: &HashMap<u64, Vec<u64>>) -> bool {
     ordered
 }
 
+/// Moves the pages so that every rule between them is followed. Each position takes the first
+/// remaining page that no other remaining page must precede, so that the pages without rules
+/// keep their relative order
 fn fix_unordered(update: &mut [u64], rules: &HashMap<u64, Vec<u64>>) {
-    while !is_ordered(update, rules) {
-        let cur_update = Vec::from(&mut *update);
-        for (left_idx, pair) in cur_update.windows(2).enumerate() {
-            if let Some(right) = pair.last() {
-                if let Some(after) = rules.get(pair.first().unwrap()) {
-                    if !after.contains(right) {
-                        let right_idx = match left_idx + 1 > update.len() {
-                            true => update.len(),
-                            false => left_idx + 1,
-                        };
-                        update.swap(left_idx, right_idx);
-                    }
-                }
-            }
-        }
+    let must_precede =
+        |before: &u64, page: &u64| rules.get(before).is_some_and(|after| after.contains(page));
+
+    for idx in 0..update.len() {
+        let remaining = &update[idx..];
+        let next = (0..remaining.len())
+            .find(|&candidate| {
+                !remaining
+                    .iter()
+                    .any(|page| must_precede(page, &remaining[candidate]))
+            })
+            // The rules of the remaining pages form a cycle, they cannot be ordered
+            .unwrap_or(0);
+        update[idx..=idx + next].rotate_right(1);
     }
 }
 
@@ -202,7 +228,7 @@ mod test {
             75,97,47,61,53
             61,13,29
             97,13,75,29,47";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day05::solve(input).unwrap();
         assert_eq!(p1, Solution::from(143_u64));
         assert_eq!(p2, Solution::from(123_u64));
     }
@@ -243,4 +269,19 @@ mod test {
             assert!(is_ordered(&update, &rules));
         }
     }
+
+    #[test]
+    fn pages_without_rules() {
+        let rules = HashMap::from([(1, vec![2]), (3, vec![4])]);
+
+        let mut update = vec![2, 1, 5];
+        fix_unordered(&mut update, &rules);
+        assert_eq!(update, [1, 2, 5]);
+
+        // The pages without rules in between used to block the swaps forever
+        let mut update = vec![5, 2, 7, 1, 4, 6, 3];
+        fix_unordered(&mut update, &rules);
+        assert!(is_ordered(&update, &rules));
+        assert_eq!(update, [5, 7, 1, 2, 6, 3, 4]);
+    }
 }
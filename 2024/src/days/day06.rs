@@ -1,82 +1,121 @@
-use std::{collections::HashMap, fmt::Display, thread::sleep, time::Duration};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Write},
+    time::Duration,
+};
 
-use crate::{Solution, SolutionPair};
+use tracing::{debug, trace};
+
+use crate::etc::Visualizer;
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut input_matrix: Vec<Vec<char>> = input
-        .trim()
-        .lines()
-        .map(|l| l.trim().chars().collect())
-        .collect();
+pub struct Day06;
+
+register_day!(Day06, 2024, 6, "Guard Gallivant");
+
+impl Day for Day06 {
+    /// The map and the guard in its starting position
+    type Parsed = (Vec<Vec<char>>, Guard);
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        let input_matrix: Vec<Vec<char>> = input
+            .trim()
+            .lines()
+            .map(|l| l.trim().chars().collect())
+            .collect();
+
+        let mut guard = None;
+
+        for (y, line) in input_matrix.iter().enumerate() {
+            match line
+                .iter()
+                .position(|&c| c == '^' || c == '<' || c == 'v' || c == '>')
+            {
+                Some(x) => {
+                    guard = Some(Guard {
+                        position: (x, y),
+                        direction: Direction::try_from(input_matrix[y][x]).unwrap_or_default(),
+                    });
+                    break;
+                }
+                None => continue,
+            }
+        }
+        let guard = guard.ok_or_else(|| AocError::invalid_input("there is no guard on the map"))?;
 
-    let mut guard = Guard::default();
+        Ok((input_matrix, guard))
+    }
 
-    for (y, line) in input_matrix.iter().enumerate() {
-        match line
-            .iter()
-            .position(|&c| c == '^' || c == '<' || c == 'v' || c == '>')
-        {
-            Some(x) => {
-                guard.position.0 = x;
-                guard.position.1 = y;
-                guard.direction = Direction::try_from(input_matrix[y][x]).unwrap_or_default();
-                break;
+    fn part1((input_matrix, guard): &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let visualizer = &ctx.visualizer;
+        let sol1: u64 = patrol(&mut input_matrix.clone(), *guard, visualizer);
+
+        Ok(Solution::from(sol1))
+    }
+
+    fn part2((input_matrix, guard): &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        // Only the cells on the patrolled path can make the guard loop
+        let visualizer = &ctx.visualizer;
+        let mut input_matrix = input_matrix.clone();
+        let visited = patrol(&mut input_matrix, *guard, visualizer);
+        debug!(visited, "obstructions tried on the patrolled cells");
+
+        let mut sol2: u64 = 0;
+
+        for (y, line) in input_matrix.iter().enumerate() {
+            for (x, &cell) in line.iter().enumerate() {
+                if cell == '-' || cell == '|' || cell == '+' {
+                    ctx.cancel.check()?;
+                    let mut input_matrix = input_matrix.clone();
+                    input_matrix[y][x] = '#';
+                    if is_guard_looping(&mut guard.clone(), &mut input_matrix, visualizer) {
+                        trace!(x, y, "obstruction makes the guard loop");
+                        sol2 += 1;
+                    }
+                }
             }
-            None => continue,
         }
-    }
 
-    let guard_p2 = guard;
+        Ok(Solution::from(sol2))
+    }
+}
 
-    let mut sol1: u64 = 0;
+/// Moves the guard until it leaves the map, marking the visited cells, and returns the number of
+/// distinct cells visited
+fn patrol(input_matrix: &mut [Vec<char>], mut guard: Guard, visualizer: &Visualizer) -> u64 {
+    let mut visited: u64 = 0;
     loop {
-        sol1 += guard.toggle_cell(&mut input_matrix) as u64;
+        visited += guard.toggle_cell(input_matrix) as u64;
 
-        #[cfg(all(not(test), feature = "visualize"))]
-        _print_map(&input_matrix, &guard, &(sol1 as usize));
+        visualizer.frame(FRAME_DELAY, |f| draw_map(f, input_matrix, &guard, visited));
 
-        if get_next_cell(&guard, &mut input_matrix, 1).is_none() {
+        if get_next_cell(&guard, input_matrix, 1).is_none() {
             break;
         }
 
-        guard.forward(&mut input_matrix);
+        guard.forward(input_matrix);
     }
 
     if input_matrix.len() <= 1 {
-        sol1 = 1;
+        visited = 1;
     }
 
-    let mut sol2: u64 = 0;
-
-    for (y, line) in input_matrix.iter().enumerate() {
-        for (x, _) in line.iter().enumerate() {
-            let mut input_matrix = input_matrix.clone();
-            let cell: &mut char = input_matrix
-                .get_mut(y)
-                .and_then(|line| line.get_mut(x))
-                .unwrap();
-            if *cell == '-' || *cell == '|' || *cell == '+' {
-                *cell = '#';
-                if is_guard_looping(&mut guard_p2.clone(), &mut input_matrix) {
-                    sol2 += 1;
-                }
-            }
-        }
-    }
-
-    (Solution::from(sol1), Solution::from(sol2))
+    visited
 }
 
-fn is_guard_looping(guard: &mut Guard, input_matrix: &mut [Vec<char>]) -> bool {
+fn is_guard_looping(
+    guard: &mut Guard,
+    input_matrix: &mut [Vec<char>],
+    visualizer: &Visualizer,
+) -> bool {
     let mut visited_positions: HashMap<((usize, usize), Direction), u64> = HashMap::new();
     loop {
         let visits = visited_positions
             .entry((guard.position, guard.direction))
             .or_insert(0);
-        #[cfg(all(not(test), feature = "visualize"))]
-        _print_map(&input_matrix, &guard, &(*visits as usize));
+        visualizer.frame(FRAME_DELAY, |f| draw_map(f, input_matrix, guard, *visits));
         *visits += 1;
 
         if *visits > 1 {
@@ -89,22 +128,22 @@ fn is_guard_looping(guard: &mut Guard, input_matrix: &mut [Vec<char>]) -> bool {
     }
 }
 
-fn _print_map(input_matrix: &[Vec<char>], guard: &Guard, visits: &usize) {
-    print!("{}[2J", 27 as char);
+const FRAME_DELAY: Duration = Duration::from_millis(100);
+
+fn draw_map(f: &mut String, input_matrix: &[Vec<char>], guard: &Guard, visits: u64) -> fmt::Result {
     for (y, line) in input_matrix.iter().enumerate() {
         for (x, char) in line.iter().enumerate() {
             if guard.position == (x, y) {
-                print!("{}", guard.direction);
+                write!(f, "{}", guard.direction)?;
             } else if *char == '.' {
-                print!(" ");
+                write!(f, " ")?;
             } else {
-                print!("{char}");
+                write!(f, "{char}")?;
             }
         }
-        println!();
+        writeln!(f)?;
     }
-    println!("Visits: {}", visits);
-    sleep(Duration::from_millis(100));
+    writeln!(f, "Visits: {}", visits)
 }
 
 #[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
@@ -155,8 +194,8 @@ impl Direction {
     }
 }
 
-#[derive(Default, Clone, Copy)]
-struct Guard {
+#[derive(Clone, Copy)]
+pub struct Guard {
     position: (usize, usize),
     direction: Direction,
 }
@@ -267,7 +306,7 @@ mod test {
             ........#.
             #.........
             ......#...";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day06::solve(input).unwrap();
         assert_eq!(p1, Solution::from(41_u64));
         assert_eq!(p2, Solution::from(6_u64));
     }
@@ -275,7 +314,7 @@ mod test {
     #[test]
     fn one_cell() {
         let input = "^";
-        let (p1, _) = solve(input);
+        let (p1, _) = Day06::solve(input).unwrap();
         assert_eq!(p1, Solution::from(1_u64));
     }
 
@@ -286,7 +325,7 @@ mod test {
             .
             ^
             ";
-        let (p1, _) = solve(input);
+        let (p1, _) = Day06::solve(input).unwrap();
         assert_eq!(p1, Solution::from(2_u64));
     }
 
@@ -297,7 +336,7 @@ mod test {
             #<.
             ...
             ";
-        let (p1, _) = solve(input);
+        let (p1, _) = Day06::solve(input).unwrap();
         assert_eq!(p1, Solution::from(2_u64));
     }
 
@@ -310,7 +349,7 @@ mod test {
             ...#.
             .....
             ";
-        let (p1, _) = solve(input);
+        let (p1, _) = Day06::solve(input).unwrap();
         assert_eq!(p1, Solution::from(4_u64));
     }
 
@@ -323,7 +362,15 @@ mod test {
             .....
             ..#..
             ";
-        let (p1, _) = solve(input);
+        let (p1, _) = Day06::solve(input).unwrap();
         assert_eq!(p1, Solution::from(3_u64));
     }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            Day06::parse("..#\n..."),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
 }
@@ -1,52 +1,76 @@
-use crate::{Solution, SolutionPair};
+use tracing::trace;
+
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let equations: Vec<(u64, Vec<u64>)> = input
-        .trim()
-        .lines()
-        .map(|l| {
-            let (result, numbers) = l.trim().split_once(":").unwrap_or_default();
-            (
-                result.trim().parse().unwrap_or_default(),
-                numbers
-                    .trim()
-                    .split(" ")
-                    .map(|n| n.trim().parse().unwrap_or_default())
-                    .collect(),
-            )
-        })
-        .collect();
-
-    let mut sol1: u64 = 0;
-    let mut sol2: u64 = 0;
-
-    for equation in &equations {
-        if is_equation_valid(equation, &[Operator::Add, Operator::Multiply]) {
-            sol1 += equation.0;
-        }
+pub struct Day07;
+
+register_day!(Day07, 2024, 7, "Bridge Repair");
+
+impl Day for Day07 {
+    /// The test value and the numbers of each equation
+    type Parsed = Vec<(u64, Vec<u64>)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        Ok(input
+            .trim()
+            .lines()
+            .map(|l| {
+                let (result, numbers) = l.trim().split_once(":").unwrap_or_default();
+                (
+                    result.trim().parse().unwrap_or_default(),
+                    numbers
+                        .trim()
+                        .split(" ")
+                        .map(|n| n.trim().parse().unwrap_or_default())
+                        .collect(),
+                )
+            })
+            .collect())
+    }
+
+    fn part1(equations: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut sol1: u64 = 0;
 
-        if is_equation_valid(
-            equation,
-            &[Operator::Add, Operator::Multiply, Operator::Concatenate],
-        ) {
-            sol2 += equation.0;
+        for equation in equations {
+            if is_equation_valid(equation, &[Operator::Add, Operator::Multiply]) {
+                trace!(test_value = equation.0, "equation can be true");
+                sol1 += equation.0;
+            }
         }
+
+        Ok(Solution::from(sol1))
     }
 
-    (Solution::from(sol1), Solution::from(sol2))
+    fn part2(equations: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut sol2: u64 = 0;
+
+        for equation in equations {
+            if is_equation_valid(
+                equation,
+                &[Operator::Add, Operator::Multiply, Operator::Concatenate],
+            ) {
+                trace!(test_value = equation.0, "equation can be true");
+                sol2 += equation.0;
+            }
+        }
+
+        Ok(Solution::from(sol2))
+    }
 }
 
 #[derive(PartialEq, Default, Debug)]
-enum Operator {
+pub enum Operator {
     #[default]
     Add,
     Multiply,
     Concatenate,
 }
 
-fn is_equation_valid(equation: &(u64, Vec<u64>), operators: &[Operator]) -> bool {
+/// Whether some combination of the operators makes the numbers add up to the test value,
+/// benched in `benches/hot.rs`
+pub fn is_equation_valid(equation: &(u64, Vec<u64>), operators: &[Operator]) -> bool {
     // Operators is a mask where each digit represents a operator
     let mut mask: usize = operators.len().pow(equation.1.len() as u32 - 1) - 1;
 
@@ -137,7 +161,7 @@ mod test {
             21037: 9 7 18 13
             292: 11 6 16 20";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day07::solve(input).unwrap();
         assert_eq!(p1, Solution::from(3749_u64));
         assert_eq!(p2, Solution::from(11387_u64));
     }
@@ -146,7 +170,7 @@ mod test {
     fn combinations() {
         let input = "190: 10 19 1 1 1 1 1 1 1 1 1";
 
-        let (p1, _) = solve(input);
+        let (p1, _) = Day07::solve(input).unwrap();
         assert_eq!(p1, Solution::from(190_u64));
     }
 }
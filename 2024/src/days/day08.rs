@@ -1,40 +1,69 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    time::Duration,
+};
 
-use crate::{Solution, SolutionPair};
+use tracing::{debug, trace};
+
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut antennas: HashMap<char, Vec<(usize, usize)>> = HashMap::new();
-    input.trim().lines().enumerate().for_each(|(y, l)| {
-        l.trim().chars().enumerate().for_each(|(x, c)| {
-            if c != '.' {
-                let antenna = antennas.entry(c).or_default();
-                antenna.push((x, y));
-            }
-        })
-    });
+pub struct Day08;
+
+register_day!(Day08, 2024, 8, "Resonant Collinearity");
+
+impl Day for Day08 {
+    /// The positions of each antenna frequency and the size of the map
+    type Parsed = (HashMap<char, Vec<(usize, usize)>>, (usize, usize));
 
-    let map_width = input.trim().lines().next().unwrap_or_default().trim().len();
-    let map_height = input.trim().lines().count();
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        let mut antennas: HashMap<char, Vec<(usize, usize)>> = HashMap::new();
+        input.trim().lines().enumerate().for_each(|(y, l)| {
+            l.trim().chars().enumerate().for_each(|(x, c)| {
+                if c != '.' {
+                    let antenna = antennas.entry(c).or_default();
+                    antenna.push((x, y));
+                }
+            })
+        });
+
+        let map_width = input.trim().lines().next().unwrap_or_default().trim().len();
+        let map_height = input.trim().lines().count();
+        if let Some(line) = input.trim().lines().find(|l| l.trim().len() != map_width) {
+            return Err(AocError::invalid_input(format!(
+                "`{}` is not {map_width} cells wide like the first line of the map",
+                line.trim()
+            )));
+        }
 
-    let antinodes = calculate_antinodes(&antennas, (map_width, map_height), false);
-    let sol1: u64 = antinodes.len() as u64;
+        Ok((antennas, (map_width, map_height)))
+    }
 
-    #[cfg(all(not(test), feature = "visualize"))]
-    println!("Part 1:");
-    #[cfg(all(not(test), feature = "visualize"))]
-    _print_map((map_width, map_height), &antennas, &antinodes);
+    fn part1((antennas, map_size): &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let antinodes = calculate_antinodes(antennas, *map_size, false);
+        let sol1: u64 = antinodes.len() as u64;
 
-    let antinodes = calculate_antinodes(&antennas, (map_width, map_height), true);
-    let sol2: u64 = antinodes.len() as u64;
+        ctx.visualizer.frame(FRAME_DELAY, |f| {
+            writeln!(f, "Part 1:")?;
+            draw_map(f, *map_size, antennas, &antinodes)
+        });
+
+        Ok(Solution::from(sol1))
+    }
 
-    #[cfg(all(not(test), feature = "visualize"))]
-    println!("\nPart 2:");
-    #[cfg(all(not(test), feature = "visualize"))]
-    _print_map((map_width, map_height), &antennas, &antinodes);
+    fn part2((antennas, map_size): &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let antinodes = calculate_antinodes(antennas, *map_size, true);
+        let sol2: u64 = antinodes.len() as u64;
 
-    (Solution::from(sol1), Solution::from(sol2))
+        ctx.visualizer.frame(FRAME_DELAY, |f| {
+            writeln!(f, "Part 2:")?;
+            draw_map(f, *map_size, antennas, &antinodes)
+        });
+
+        Ok(Solution::from(sol2))
+    }
 }
 
 fn calculate_antinodes(
@@ -43,7 +72,8 @@ fn calculate_antinodes(
     resonance: bool,
 ) -> Vec<(usize, usize)> {
     let mut antinodes: Vec<(usize, usize)> = vec![];
-    antennas.iter().for_each(|(_, positions)| {
+    antennas.iter().for_each(|(frequency, positions)| {
+        let before = antinodes.len();
         for antenna in positions {
             for other in positions {
                 if other == antenna {
@@ -101,15 +131,30 @@ fn calculate_antinodes(
                 }
             }
         }
+        trace!(
+            %frequency,
+            antennas = positions.len(),
+            antinodes = antinodes.len() - before,
+            "new antinodes of the frequency"
+        );
     });
+    debug!(
+        frequencies = antennas.len(),
+        antinodes = antinodes.len(),
+        "antinodes found"
+    );
     antinodes
 }
 
-fn _print_map(
+/// Both maps are shown for a while, since there is a single frame per part
+const FRAME_DELAY: Duration = Duration::from_secs(1);
+
+fn draw_map(
+    f: &mut String,
     (map_width, map_height): (usize, usize),
     antennas: &HashMap<char, Vec<(usize, usize)>>,
     antinodes: &[(usize, usize)],
-) {
+) -> fmt::Result {
     let mut map: Vec<Vec<char>> = vec![];
     for y in 0..map_height {
         map.push(vec![]);
@@ -119,31 +164,27 @@ fn _print_map(
     }
 
     for antinode in antinodes {
-        let cell: &mut char = map
-            .get_mut(antinode.1)
-            .unwrap()
-            .get_mut(antinode.0)
-            .unwrap();
-        *cell = '#';
+        if let Some(cell) = map.get_mut(antinode.1).and_then(|l| l.get_mut(antinode.0)) {
+            *cell = '#';
+        }
     }
 
     for antenna in antennas {
         for position in antenna.1 {
-            let cell: &mut char = map
-                .get_mut(position.1)
-                .unwrap()
-                .get_mut(position.0)
-                .unwrap();
-            *cell = *antenna.0;
+            if let Some(cell) = map.get_mut(position.1).and_then(|l| l.get_mut(position.0)) {
+                *cell = *antenna.0;
+            }
         }
     }
 
     for line in map {
         for char in line {
-            print!("{char}");
+            write!(f, "{char}")?;
         }
-        println!();
+        writeln!(f)?;
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -164,8 +205,16 @@ mod test {
             ............
             ............";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day08::solve(input).unwrap();
         assert_eq!(p1, Solution::from(14_u64));
         assert_eq!(p2, Solution::from(34_u64));
     }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            Day08::parse("..a.\n..a..\n...."),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
 }
@@ -1,99 +1,112 @@
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{self, Debug, Display, Write},
+    time::Duration,
+};
 
-#[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-use std::{thread::sleep, time::Duration};
+use tracing::{debug, trace};
 
-use crate::{Solution, SolutionPair};
+use crate::etc::Visualizer;
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut input = input.trim().to_string();
-    if input.len() % 2 != 0 {
-        input.push('0');
+pub struct Day09;
+
+register_day!(Day09, 2024, 9, "Disk Fragmenter");
+
+impl Day for Day09 {
+    type Parsed = Vec<File>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        let digits = input
+            .trim()
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(u64::from)
+                    .ok_or_else(|| AocError::invalid_input(format!("`{c}` is not a digit")))
+            })
+            .collect::<Result<Vec<u64>, AocError>>()?;
+
+        // The last file has no free space after it
+        Ok(digits
+            .chunks(2)
+            .enumerate()
+            .map(|(id, pair)| File::new(id as u64, pair[0], pair.get(1).copied().unwrap_or(0)))
+            .collect())
     }
 
-    let mut files: Vec<File> = input
-        .trim()
-        .chars()
-        .collect::<Vec<char>>()
-        .windows(2)
-        .step_by(2)
-        .enumerate()
-        .map(|(id, pair)| {
-            let size = pair
-                .first()
-                .unwrap_or(&'0')
-                .to_string()
-                .parse()
-                .unwrap_or_default();
-            let free = pair
-                .last()
-                .unwrap_or(&'0')
-                .to_string()
-                .parse()
-                .unwrap_or_default();
-            File::new(id as u64, size, free)
-        })
-        .collect();
-
-    let mut files_p1 = files.clone();
-    order_files_fragments(&mut files_p1);
-    let sol1: u64 = calculate_checksum(&files_p1);
-
-    order_files(&mut files);
-    let sol2: u64 = calculate_checksum(&files);
-
-    (Solution::from(sol1), Solution::from(sol2))
+    fn part1(files: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let mut files = files.clone();
+        order_files_fragments(&mut files, &ctx.visualizer);
+        let sol1: u64 = calculate_checksum(&files);
+
+        Ok(Solution::from(sol1))
+    }
+
+    fn part2(files: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let mut files = files.clone();
+        order_files(&mut files, &ctx.visualizer);
+        let sol2: u64 = calculate_checksum(&files);
+
+        Ok(Solution::from(sol2))
+    }
 }
 
-fn _print_files(files: &Vec<File>) {
+const FRAME_DELAY: Duration = Duration::from_millis(3);
+
+fn draw_files(f: &mut String, files: &[File]) -> fmt::Result {
     for file in files {
-        print!("{}", file);
+        write!(f, "{}", file)?;
     }
-    println!();
+    writeln!(f)
 }
 
-fn order_files_fragments(files: &mut Vec<File>) {
-    loop {
-        #[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-        _print_files(files);
-        #[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-        sleep(Duration::from_millis(3));
-
-        let last_file: &File = match files.iter().rev().find(|f| f.size > 0) {
-            Some(file) => file,
-            None => return,
-        };
-        let first_free = match files.iter().find(|f| f.free > 0) {
-            Some(file) => file,
-            None => return,
-        };
+/// The disk with the id of the file in each of its blocks and a `.` in the free ones, logged
+/// with each move
+struct Blocks<'a>(&'a [File]);
 
-        if (last_file == first_free
-            && files.len() - 1 - files.iter().rev().position(|f| f == last_file).unwrap()
-                == files.iter().position(|f| f == first_free).unwrap())
-            || files.len() - 1 - files.iter().rev().position(|f| f == last_file).unwrap()
-                <= files.iter().position(|f| f == first_free).unwrap()
-        {
-            return;
+impl Display for Blocks<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for file in self.0 {
+            for _ in 0..file.size {
+                write!(f, "{}", file.id)?;
+            }
+            for _ in 0..file.free {
+                write!(f, ".")?;
+            }
         }
 
-        let last_file: &mut File = files.iter_mut().rev().find(|f| f.size > 0).unwrap();
+        Ok(())
+    }
+}
 
-        last_file.size = match last_file.size.checked_sub(1) {
-            Some(size) => size,
-            None => return,
+fn order_files_fragments(files: &mut Vec<File>, visualizer: &Visualizer) {
+    let mut moves: u64 = 0;
+    loop {
+        visualizer.frame(FRAME_DELAY, |f| draw_files(f, files));
+        trace!(disk = %Blocks(files), "disk layout");
+
+        let (Some(last_idx), Some(free_idx)) = (
+            files.iter().rposition(|f| f.size > 0),
+            files.iter().position(|f| f.free > 0),
+        ) else {
+            break;
         };
+
+        // Every free block is after the last file block
+        if last_idx <= free_idx {
+            break;
+        }
+        moves += 1;
+
+        let last_file: &mut File = &mut files[last_idx];
+        last_file.size -= 1;
         last_file.free += 1;
 
         let last_id = last_file.id;
 
-        let free_idx = files.iter().position(|f| f.free > 0).unwrap() + 1;
-        let first_free = match files.iter_mut().find(|f| f.free > 0) {
-            Some(file) => file,
-            None => return,
-        };
+        let first_free: &mut File = &mut files[free_idx];
 
         if first_free.id == last_id {
             first_free.size += 1;
@@ -103,19 +116,19 @@ fn order_files_fragments(files: &mut Vec<File>) {
 
             first_free.free = 0;
 
-            files.insert(free_idx, new_file);
+            files.insert(free_idx + 1, new_file);
         }
     }
+    debug!(moves, "blocks moved to the free space");
 }
 
-fn order_files(files: &mut Vec<File>) {
+fn order_files(files: &mut Vec<File>, visualizer: &Visualizer) {
     let current_order = files.clone();
-    let mut files_len = files.len() - 1;
+    let mut files_len = files.len().saturating_sub(1);
+    let mut moves: u64 = 0;
     for (idx, _) in current_order.iter().rev().enumerate() {
-        #[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-        _print_files(files);
-        #[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-        sleep(Duration::from_millis(3));
+        visualizer.frame(FRAME_DELAY, |f| draw_files(f, files));
+        trace!(disk = %Blocks(files), "disk layout");
 
         let last_file = files.remove(files_len.saturating_sub(idx));
         if last_file.size == 0 {
@@ -129,41 +142,28 @@ fn order_files(files: &mut Vec<File>) {
         {
             Some(file) => file,
             None => {
+                trace!(id = last_file.id, "no free space for the file");
                 files.insert(files_len.saturating_sub(idx), last_file);
                 continue;
             }
         };
+        trace!(id = last_file.id, size = last_file.size, "file moved");
+        moves += 1;
 
-        let prev = files.get(files_len.saturating_sub(idx + 1)).unwrap();
-        let first_free: &File = files[0..files_len.saturating_sub(idx)]
-            .iter()
-            .find(|f| f.free >= last_file.size)
-            .unwrap();
-
-        if prev == first_free {
-            let first_free: &mut File = files[0..files_len.saturating_sub(idx)]
-                .iter_mut()
-                .find(|f| f.free >= last_file.size)
-                .unwrap();
-
-            let free_space = first_free.free;
-            first_free.free = 0;
+        // The file right before the moved one, which gets its space back. It is `free_idx` at
+        // the earliest, so it exists
+        let prev_idx = files_len.saturating_sub(idx + 1);
+        let first_free: &mut File = &mut files[free_idx];
+        let free_space = first_free.free;
+        first_free.free = 0;
 
+        if prev_idx == free_idx {
             files.insert(
                 free_idx + 1,
                 File::new(last_file.id, last_file.size, free_space + last_file.free),
             );
         } else {
-            let first_free: &mut File = files[0..files_len.saturating_sub(idx)]
-                .iter_mut()
-                .find(|f| f.free >= last_file.size)
-                .unwrap();
-
-            let free_space = first_free.free;
-            first_free.free = 0;
-
-            let prev: &mut File = files.get_mut(files_len.saturating_sub(idx + 1)).unwrap();
-            prev.free += last_file.size + last_file.free;
+            files[prev_idx].free += last_file.size + last_file.free;
 
             files.insert(
                 free_idx + 1,
@@ -173,6 +173,7 @@ fn order_files(files: &mut Vec<File>) {
 
         files_len += 1;
     }
+    debug!(moves, "files moved to the free space");
 }
 
 fn calculate_checksum(files: &[File]) -> u64 {
@@ -190,7 +191,7 @@ fn calculate_checksum(files: &[File]) -> u64 {
 }
 
 #[derive(PartialEq, Copy, Clone, Default)]
-struct File {
+pub struct File {
     id: u64,
     size: u64,
     free: u64,
@@ -202,19 +203,14 @@ impl File {
     }
 }
 
+/// The blocks of the file then its free space
 impl Display for File {
-    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for _ in 0..self.size {
-            #[cfg(all(not(test), feature = "visualize"))]
-            write!(_f, "█")?;
-            #[cfg(all(not(test), feature = "debug"))]
-            write!(_f, "{}", self.id)?;
+            write!(f, "█")?;
         }
         for _ in 0..self.free {
-            #[cfg(all(not(test), feature = "visualize"))]
-            write!(_f, " ")?;
-            #[cfg(all(not(test), feature = "debug"))]
-            write!(_f, ".")?;
+            write!(f, " ")?;
         }
 
         Ok(())
@@ -233,7 +229,7 @@ mod test {
     #[test]
     fn small() {
         let input = "12345";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day09::solve(input).unwrap();
         assert_eq!(p1, Solution::from(60_u64));
         assert_eq!(p2, Solution::from(132_u64));
     }
@@ -241,22 +237,36 @@ mod test {
     #[test]
     fn medium() {
         let input = "233313312141413140211";
-        let (p1, _) = solve(input);
+        let (p1, _) = Day09::solve(input).unwrap();
         assert_eq!(p1, Solution::from(2132_u64));
     }
 
     #[test]
     fn medium2() {
         let input = "111010101010101010101";
-        let (p1, _) = solve(input);
+        let (p1, _) = Day09::solve(input).unwrap();
         assert_eq!(p1, Solution::from(340_u64));
     }
 
     #[test]
     fn aoc_test() {
         let input = "2333133121414131402";
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day09::solve(input).unwrap();
         assert_eq!(p1, Solution::from(1928_u64));
         assert_eq!(p2, Solution::from(2858_u64));
     }
+
+    #[test]
+    fn blocks() {
+        let files = Day09::parse("12345").unwrap();
+        assert_eq!(Blocks(&files).to_string(), "0..111....22222");
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            Day09::parse("12a45"),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
 }
@@ -1,49 +1,86 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    time::Duration,
+};
 
-#[cfg(all(not(test), feature = "visualize"))]
-use std::{thread::sleep, time::Duration};
+use tracing::trace;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::Visualizer;
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let map: Vec<Vec<usize>> = input
-        .trim()
-        .lines()
-        .map(|l| {
-            l.trim()
-                .chars()
-                .map(|c| c.to_string().parse().unwrap_or(11))
-                .collect()
-        })
-        .collect();
-
-    let mut sol1: u64 = 0;
-    let mut sol2: u64 = 0;
+pub struct Day10;
+
+register_day!(Day10, 2024, 10, "Hoof It");
+
+impl Day for Day10 {
+    /// The height of each cell, 11 for the impassable ones
+    type Parsed = Vec<Vec<usize>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        Ok(input
+            .trim()
+            .lines()
+            .map(|l| {
+                l.trim()
+                    .chars()
+                    .map(|c| c.to_string().parse().unwrap_or(11))
+                    .collect()
+            })
+            .collect())
+    }
 
-    for (y, row) in map.iter().enumerate() {
-        for (x, cell) in row.iter().enumerate() {
-            if *cell == 0 {
-                sol1 += calculate_score(&map, (x, y)) as u64;
-                sol2 += calculate_rating(&map, (x, y)) as u64;
+    fn part1(map: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let visualizer = &ctx.visualizer;
+        let mut sol1: u64 = 0;
+
+        for (y, row) in map.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if *cell == 0 {
+                    let score = calculate_score(map, (x, y), visualizer);
+                    trace!(x, y, score, "trailhead");
+                    sol1 += score as u64;
+                }
             }
         }
+
+        Ok(Solution::from(sol1))
     }
 
-    (Solution::from(sol1), Solution::from(sol2))
+    fn part2(map: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let visualizer = &ctx.visualizer;
+        let mut sol2: u64 = 0;
+
+        for (y, row) in map.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if *cell == 0 {
+                    let rating = calculate_rating(map, (x, y), visualizer);
+                    trace!(x, y, rating, "trailhead");
+                    sol2 += rating as u64;
+                }
+            }
+        }
+
+        Ok(Solution::from(sol2))
+    }
 }
 
-fn calculate_score(map: &[Vec<usize>], position: (usize, usize)) -> usize {
+fn calculate_score(map: &[Vec<usize>], position: (usize, usize), visualizer: &Visualizer) -> usize {
     let mut tops = HashMap::new();
-    search_tops(map, position, &mut tops);
+    search_tops(map, position, &mut tops, visualizer);
 
     tops.len()
 }
 
-fn calculate_rating(map: &[Vec<usize>], position: (usize, usize)) -> usize {
+fn calculate_rating(
+    map: &[Vec<usize>],
+    position: (usize, usize),
+    visualizer: &Visualizer,
+) -> usize {
     let mut tops = HashMap::new();
-    search_tops(map, position, &mut tops);
+    search_tops(map, position, &mut tops, visualizer);
 
     tops.values().sum()
 }
@@ -52,21 +89,24 @@ fn search_tops(
     map: &[Vec<usize>],
     position: (usize, usize),
     tops: &mut HashMap<(usize, usize), usize>,
+    visualizer: &Visualizer,
 ) {
-    let current_cell = map.get(position.1).unwrap().get(position.0).unwrap();
+    let current_cell = match map.get(position.1).and_then(|row| row.get(position.0)) {
+        Some(cell) => cell,
+        None => return,
+    };
     if current_cell == &9 {
         let top = tops.entry(position).or_insert(0);
         *top += 1;
     }
 
-    #[cfg(all(not(test), feature = "visualize"))]
-    print_map(map, position);
+    visualizer.frame(FRAME_DELAY, |f| draw_map(f, map, position));
 
     for y in [0, 2] {
         if let Some(row) = map.get((position.1 + y).saturating_sub(1)) {
             if let Some(vertical_cell) = row.get(position.0) {
                 if *vertical_cell == *current_cell + 1 {
-                    search_tops(map, (position.0, (position.1 + y) - 1), tops);
+                    search_tops(map, (position.0, (position.1 + y) - 1), tops, visualizer);
                 }
             }
         }
@@ -76,31 +116,30 @@ fn search_tops(
         if let Some(row) = map.get(position.1) {
             if let Some(horizontal_cell) = row.get((position.0 + x).saturating_sub(1)) {
                 if *horizontal_cell == *current_cell + 1 {
-                    search_tops(map, ((position.0 + x) - 1, position.1), tops);
+                    search_tops(map, ((position.0 + x) - 1, position.1), tops, visualizer);
                 }
             }
         }
     }
 }
 
-#[cfg(all(not(test), feature = "visualize"))]
-fn print_map(map: &[Vec<usize>], position: (usize, usize)) {
-    for row in 0..map.len() {
-        'next_cell: for col in 0..map.first().unwrap().len() {
-            if col == position.0 && row == position.1 {
-                print!("█");
+const FRAME_DELAY: Duration = Duration::from_millis(3);
+
+fn draw_map(f: &mut String, map: &[Vec<usize>], position: (usize, usize)) -> fmt::Result {
+    for (y, row) in map.iter().enumerate() {
+        for (x, height) in row.iter().enumerate() {
+            if (x, y) == position {
+                write!(f, "█")?;
+            } else if *height == 11 {
+                write!(f, ".")?;
             } else {
-                if map[row][col] == 11 {
-                    print!(".");
-                } else {
-                    print!("{}", map[row][col]);
-                }
+                write!(f, "{height}")?;
             }
         }
-        println!();
+        writeln!(f)?;
     }
-    println!();
-    sleep(Duration::from_millis(3));
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -114,7 +153,7 @@ mod test {
             8765
             9876";
 
-        let (p1, _) = solve(input);
+        let (p1, _) = Day10::solve(input).unwrap();
         assert_eq!(p1, Solution::from(1_u64));
     }
 
@@ -129,7 +168,7 @@ mod test {
             8.....8
             9.....9";
 
-        let (p1, _) = solve(input);
+        let (p1, _) = Day10::solve(input).unwrap();
         assert_eq!(p1, Solution::from(2_u64));
     }
 
@@ -144,7 +183,7 @@ mod test {
             876....
             987....";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day10::solve(input).unwrap();
         assert_eq!(p1, Solution::from(4_u64));
         assert_eq!(p2, Solution::from(13_u64));
     }
@@ -160,7 +199,7 @@ mod test {
             ...9..2
             .....01";
 
-        let (p1, _) = solve(input);
+        let (p1, _) = Day10::solve(input).unwrap();
         assert_eq!(p1, Solution::from(3_u64));
     }
 
@@ -175,7 +214,7 @@ mod test {
             ..8765.
             ..9....";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day10::solve(input).unwrap();
         assert_eq!(p1, Solution::from(1_u64));
         assert_eq!(p2, Solution::from(3_u64));
     }
@@ -190,7 +229,7 @@ mod test {
             4.6789
             56789.";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day10::solve(input).unwrap();
         assert_eq!(p1, Solution::from(2_u64));
         assert_eq!(p2, Solution::from(227_u64));
     }
@@ -207,7 +246,7 @@ mod test {
             01329801
             10456732";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day10::solve(input).unwrap();
         assert_eq!(p1, Solution::from(36_u64));
         assert_eq!(p2, Solution::from(81_u64));
     }
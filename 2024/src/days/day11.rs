@@ -1,34 +1,57 @@
 use std::collections::HashMap;
 
-use crate::{Solution, SolutionPair};
+use tracing::trace;
+
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut stones: HashMap<u64, u64> = HashMap::new();
+pub struct Day11;
+
+register_day!(Day11, 2024, 11, "Plutonian Pebbles");
 
-    input.trim().split(" ").for_each(|n| {
-        let num = n.parse().unwrap();
-        *stones.entry(num).or_default() += 1;
-    });
+impl Day for Day11 {
+    /// How many stones are engraved with each number
+    type Parsed = HashMap<u64, u64>;
 
-    let mut stones_p1 = stones.clone();
-    for _ in 0..25 {
-        stones_p1 = blink(stones_p1);
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        let mut stones: HashMap<u64, u64> = HashMap::new();
+
+        for n in input.trim().split(" ") {
+            let num = n.parse()?;
+            *stones.entry(num).or_default() += 1;
+        }
+
+        Ok(stones)
     }
 
-    let sol1: u64 = stones_p1.values().sum();
+    fn part1(stones: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut stones = stones.clone();
+        for blinks in 1..=25 {
+            stones = blink(stones);
+            trace!(blinks, distinct = stones.len(), "engraved numbers");
+        }
+
+        let sol1: u64 = stones.values().sum();
 
-    for _ in 0..75 {
-        stones = blink(stones);
+        Ok(Solution::from(sol1))
     }
 
-    let sol2: u64 = stones.values().sum();
+    fn part2(stones: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut stones = stones.clone();
+        for blinks in 1..=75 {
+            stones = blink(stones);
+            trace!(blinks, distinct = stones.len(), "engraved numbers");
+        }
+
+        let sol2: u64 = stones.values().sum();
 
-    (Solution::from(sol1), Solution::from(sol2))
+        Ok(Solution::from(sol2))
+    }
 }
 
-fn blink(stones: HashMap<u64, u64>) -> HashMap<u64, u64> {
+/// The stones after one more blink, benched in `benches/hot.rs`
+pub fn blink(stones: HashMap<u64, u64>) -> HashMap<u64, u64> {
     let mut next_blink: HashMap<u64, u64> = HashMap::with_capacity(stones.len());
 
     for (stone, amount) in stones {
@@ -87,8 +110,16 @@ mod test {
     fn aoc_test() {
         let input = "125 17";
 
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day11::solve(input).unwrap();
         assert_eq!(p1, Solution::from(55312_u64));
         assert_eq!(p2, Solution::from(65601038650482_u64));
     }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            Day11::solve("125 a"),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
 }
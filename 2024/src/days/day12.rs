@@ -1,26 +1,64 @@
-use std::collections::HashSet;
-
-#[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
 use std::{
-    io::{stdout, Write},
-    thread::sleep,
+    collections::HashSet,
+    fmt::{self, Write},
     time::Duration,
 };
 
-#[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
 use owo_colors::OwoColorize;
+use tracing::trace;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::Visualizer;
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let garden = parse_input(input);
+pub struct Day12;
+
+register_day!(Day12, 2024, 12, "Garden Groups");
+
+impl Day for Day12 {
+    type Parsed = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(garden: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let price: usize = plots(garden, &ctx.visualizer)
+            .iter()
+            .map(|(plot_area, plot_perimeter, _)| plot_area * plot_perimeter)
+            .sum();
+
+        let sol1: u64 = price as u64;
+
+        Ok(Solution::from(sol1))
+    }
+
+    fn part2(garden: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let visualizer = &ctx.visualizer;
+        let discount_price: usize = plots(garden, visualizer)
+            .iter()
+            .map(|(plot_area, _, plot_positions)| {
+                let sides = get_sides(garden, plot_positions, visualizer);
+                trace!(plot_area, sides, "plot sides");
+                plot_area * sides
+            })
+            .sum();
+
+        let sol2: u64 = discount_price as u64;
+
+        Ok(Solution::from(sol2))
+    }
+}
+
+/// The area, the perimeter and the positions of a plot
+type Plot = (usize, usize, HashSet<(usize, usize)>);
 
+/// Returns every plot in the garden
+fn plots(garden: &[Vec<char>], visualizer: &Visualizer) -> Vec<Plot> {
     let mut visited_positions: HashSet<(usize, usize)> = HashSet::new();
+    let mut plots = Vec::new();
 
-    let mut price = 0;
-    let mut discount_price = 0;
     for (y, line) in garden.iter().enumerate() {
         for (x, cell) in line.iter().enumerate() {
             if visited_positions.contains(&(x, y)) {
@@ -28,25 +66,21 @@ pub fn solve(input: &str) -> SolutionPair {
             }
             let mut plot_visited_positions: HashSet<(usize, usize)> = HashSet::new();
 
-            let (plot_area, plot_perimeter) =
-                get_plot_area_and_perimeter(&garden, (x, y), cell, &mut plot_visited_positions);
-
-            let sides = get_sides(
-                #[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-                &garden,
-                &plot_visited_positions,
+            let (plot_area, plot_perimeter) = get_plot_area_and_perimeter(
+                garden,
+                (x, y),
+                cell,
+                &mut plot_visited_positions,
+                visualizer,
             );
-            visited_positions.extend(plot_visited_positions);
 
-            price += plot_area * plot_perimeter;
-            discount_price += plot_area * sides;
+            trace!(plant = %cell, x, y, plot_area, plot_perimeter, "plot");
+            visited_positions.extend(plot_visited_positions.iter());
+            plots.push((plot_area, plot_perimeter, plot_visited_positions));
         }
     }
 
-    let sol1: u64 = price as u64;
-    let sol2: u64 = discount_price as u64;
-
-    (Solution::from(sol1), Solution::from(sol2))
+    plots
 }
 
 fn parse_input(input: &str) -> Vec<Vec<char>> {
@@ -63,6 +97,7 @@ fn get_plot_area_and_perimeter(
     position: (usize, usize),
     plot_type: &char,
     visited_positions: &mut HashSet<(usize, usize)>,
+    visualizer: &Visualizer,
 ) -> (usize, usize) {
     let mut area = 1;
     let mut perimeter = 4;
@@ -73,8 +108,9 @@ fn get_plot_area_and_perimeter(
     } else {
         visited_positions.insert(position);
 
-        #[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-        print_garden(garden, visited_positions);
+        visualizer.frame(Duration::from_millis(3), |f| {
+            draw_garden(f, garden, visited_positions)
+        });
     }
 
     // Search for more area vertically
@@ -94,6 +130,7 @@ fn get_plot_area_and_perimeter(
                         (position.0, next_y),
                         plot_type,
                         visited_positions,
+                        visualizer,
                     );
                     area += next_area;
                     perimeter += next_perimeter;
@@ -119,6 +156,7 @@ fn get_plot_area_and_perimeter(
                         (next_x, position.1),
                         plot_type,
                         visited_positions,
+                        visualizer,
                     );
                     area += next_area;
                     perimeter += next_perimeter;
@@ -131,8 +169,9 @@ fn get_plot_area_and_perimeter(
 }
 
 fn get_sides(
-    #[cfg(all(not(test), any(feature = "visualize", feature = "debug")))] garden: &[Vec<char>],
+    garden: &[Vec<char>],
     visited_positions: &HashSet<(usize, usize)>,
+    visualizer: &Visualizer,
 ) -> usize {
     let mut edges: HashSet<(usize, usize)> = HashSet::new();
     let mut sides = 0;
@@ -154,8 +193,9 @@ fn get_sides(
         }
     }
 
-    #[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-    print_edges(garden, visited_positions, &edges);
+    visualizer.frame(Duration::from_millis(100), |f| {
+        draw_edges(f, garden, visited_positions, &edges)
+    });
 
     for edge in edges {
         let neighbours = get_neighbours_direction(edge, visited_positions);
@@ -336,10 +376,11 @@ fn get_diagonal_neighbours_direction(
     neighbours
 }
 
-#[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-fn print_garden(garden: &[Vec<char>], visited_positions: &HashSet<(usize, usize)>) {
-    let mut lock = stdout().lock();
-    println!("{}[2J", 27 as char);
+fn draw_garden(
+    f: &mut String,
+    garden: &[Vec<char>],
+    visited_positions: &HashSet<(usize, usize)>,
+) -> fmt::Result {
     for (y, line) in garden.iter().enumerate() {
         for (x, cell) in line.iter().enumerate() {
             let offset = (cell.to_ascii_uppercase() as u8 % 65 + 1) * 4;
@@ -347,35 +388,20 @@ fn print_garden(garden: &[Vec<char>], visited_positions: &HashSet<(usize, usize)
             if visited_positions.contains(&(x, y)) {
                 visited += 100;
             }
-            #[cfg(feature = "visualize")]
-            write!(
-                lock,
-                "{}",
-                "██".truecolor(visited, offset + visited, 50 + visited)
-            )
-            .unwrap();
-            #[cfg(feature = "debug")]
-            write!(
-                lock,
-                "{}",
-                cell.truecolor(visited, offset + visited, 50 + visited)
-            )
-            .unwrap();
+            draw_cell(f, (visited, offset + visited, 50 + visited))?;
         }
-        writeln!(lock).unwrap();
+        writeln!(f)?;
     }
-    stdout().flush().unwrap();
-    sleep(Duration::from_millis(3));
+
+    Ok(())
 }
 
-#[cfg(all(not(test), any(feature = "visualize", feature = "debug")))]
-fn print_edges(
+fn draw_edges(
+    f: &mut String,
     garden: &[Vec<char>],
     visited_positions: &HashSet<(usize, usize)>,
     edges: &HashSet<(usize, usize)>,
-) {
-    let mut lock = stdout().lock();
-    println!("{}[2J", 27 as char);
+) -> fmt::Result {
     for (y, line) in garden.iter().enumerate() {
         for (x, cell) in line.iter().enumerate() {
             let offset = (cell.to_ascii_uppercase() as u8 % 65 + 1) * 4;
@@ -390,15 +416,17 @@ fn print_edges(
             } else {
                 color = (0, offset, 50);
             }
-            #[cfg(feature = "visualize")]
-            write!(lock, "{}", "██".truecolor(color.0, color.1, color.2),).unwrap();
-            #[cfg(feature = "debug")]
-            write!(lock, "{}", cell.truecolor(color.0, color.1, color.2)).unwrap();
+            draw_cell(f, color)?;
         }
-        writeln!(lock).unwrap();
+        writeln!(f)?;
     }
-    stdout().flush().unwrap();
-    sleep(Duration::from_millis(100));
+
+    Ok(())
+}
+
+/// A cell of the garden in its color
+fn draw_cell(f: &mut String, (r, g, b): (u8, u8, u8)) -> fmt::Result {
+    write!(f, "{}", "██".truecolor(r, g, b))
 }
 
 #[cfg(test)]
@@ -416,14 +444,24 @@ mod test {
             OOOOO",
         );
 
-        let (o_area, o_perimeter) =
-            get_plot_area_and_perimeter(&input, (0, 0), &'O', &mut HashSet::new());
+        let (o_area, o_perimeter) = get_plot_area_and_perimeter(
+            &input,
+            (0, 0),
+            &'O',
+            &mut HashSet::new(),
+            &Visualizer::default(),
+        );
 
         assert_eq!(o_area, 21);
         assert_eq!(o_perimeter, 36);
 
-        let (x_area, x_perimeter) =
-            get_plot_area_and_perimeter(&input, (1, 1), &'X', &mut HashSet::new());
+        let (x_area, x_perimeter) = get_plot_area_and_perimeter(
+            &input,
+            (1, 1),
+            &'X',
+            &mut HashSet::new(),
+            &Visualizer::default(),
+        );
 
         assert_eq!(x_area, 1);
         assert_eq!(x_perimeter, 4);
@@ -439,7 +477,7 @@ mod test {
             BBCC
             EEEC";
 
-        let (_, p2) = solve(input);
+        let (_, p2) = Day12::solve(input).unwrap();
         assert_eq!(p2, Solution::from(80_u64));
     }
 
@@ -452,7 +490,7 @@ mod test {
             OXOXO
             OOOOO";
 
-        let (_, p2) = solve(input);
+        let (_, p2) = Day12::solve(input).unwrap();
         assert_eq!(p2, Solution::from(436_u64));
     }
 
@@ -466,7 +504,7 @@ mod test {
             ABBAAA
             AAAAAA";
 
-        let (_, p2) = solve(input);
+        let (_, p2) = Day12::solve(input).unwrap();
         assert_eq!(p2, Solution::from(368_u64));
     }
 
@@ -479,7 +517,7 @@ mod test {
             EXXXX
             EEEEE";
 
-        let (_, p2) = solve(input);
+        let (_, p2) = Day12::solve(input).unwrap();
         assert_eq!(p2, Solution::from(236_u64))
     }
 
@@ -497,7 +535,7 @@ mod test {
             MIIISIJEEE
             MMMISSJEEE";
 
-        let (p1, _) = solve(input);
+        let (p1, _) = Day12::solve(input).unwrap();
         assert_eq!(p1, Solution::from(1930_u64));
     }
 }
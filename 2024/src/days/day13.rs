@@ -1,51 +1,77 @@
-use crate::{Solution, SolutionPair};
+use tracing::trace;
+
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let machines = parse_input(input);
-
-    const TOKENS_A: u64 = 3;
-    const TOKENS_B: u64 = 1;
-    let mut total_tokens: u64 = 0;
-    for (a, b, p) in &machines {
-        let b_presses = (a.x * p.y - a.y * p.x) / (-b.x * a.y + a.x * b.y);
-        let a_presses = (p.x - b.x * b_presses) / (a.x);
-
-        if (0..=100).contains(&a_presses)
-            && (0..=100).contains(&b_presses)
-            && a_presses * a.x + b_presses * b.x == p.x
-            && a_presses * a.y + b_presses * b.y == p.y
-        {
-            total_tokens += a_presses as u64 * TOKENS_A + b_presses as u64 * TOKENS_B;
-        }
+pub struct Day13;
+
+register_day!(Day13, 2024, 13, "Claw Contraption");
+
+const TOKENS_A: u64 = 3;
+const TOKENS_B: u64 = 1;
+
+impl Day for Day13 {
+    type Parsed = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        parse_input(input)
     }
 
-    let sol1: u64 = total_tokens;
-
-    total_tokens = 0;
-    for (a, b, mut p) in machines {
-        p.x += 10000000000000;
-        p.y += 10000000000000;
-        let b_presses = (a.x * p.y - a.y * p.x) / (-b.x * a.y + a.x * b.y);
-        let a_presses = (p.x - b.x * b_presses) / (a.x);
-
-        if a_presses >= 0
-            && b_presses >= 0
-            && a_presses * a.x + b_presses * b.x == p.x
-            && a_presses * a.y + b_presses * b.y == p.y
-        {
-            println!("a{a_presses} b{b_presses}");
-            total_tokens += a_presses as u64 * TOKENS_A + b_presses as u64 * TOKENS_B;
+    fn part1(machines: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut total_tokens: u64 = 0;
+        for (a, b, p) in machines {
+            if is_degenerate(a, b) {
+                continue;
+            }
+            let b_presses = (a.x * p.y - a.y * p.x) / (-b.x * a.y + a.x * b.y);
+            let a_presses = (p.x - b.x * b_presses) / (a.x);
+
+            if (0..=100).contains(&a_presses)
+                && (0..=100).contains(&b_presses)
+                && a_presses * a.x + b_presses * b.x == p.x
+                && a_presses * a.y + b_presses * b.y == p.y
+            {
+                trace!(a_presses, b_presses, "prize won");
+                total_tokens += a_presses as u64 * TOKENS_A + b_presses as u64 * TOKENS_B;
+            }
         }
+
+        let sol1: u64 = total_tokens;
+
+        Ok(Solution::from(sol1))
     }
-    let sol2: u64 = total_tokens;
 
-    (Solution::from(sol1), Solution::from(sol2))
+    fn part2(machines: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        let mut total_tokens: u64 = 0;
+        for (a, b, p) in machines {
+            if is_degenerate(a, b) {
+                continue;
+            }
+            let p = Coordinate::new(p.x + 10000000000000, p.y + 10000000000000);
+            let b_presses = (a.x * p.y - a.y * p.x) / (-b.x * a.y + a.x * b.y);
+            let a_presses = (p.x - b.x * b_presses) / (a.x);
+
+            if a_presses >= 0
+                && b_presses >= 0
+                && a_presses * a.x + b_presses * b.x == p.x
+                && a_presses * a.y + b_presses * b.y == p.y
+            {
+                trace!(a_presses, b_presses, "prize won");
+                total_tokens += a_presses as u64 * TOKENS_A + b_presses as u64 * TOKENS_B;
+            }
+        }
+        let sol2: u64 = total_tokens;
+
+        Ok(Solution::from(sol2))
+    }
 }
 
+/// The movement of the A and B buttons and the prize position of a machine
+type Machine = (Coordinate<i64>, Coordinate<i64>, Coordinate<i64>);
+
 #[derive(Debug)]
-struct Coordinate<T> {
+pub struct Coordinate<T> {
     pub x: T,
     pub y: T,
 }
@@ -56,57 +82,46 @@ impl<T> Coordinate<T> {
     }
 }
 
-fn parse_input(input: &str) -> Vec<(Coordinate<i64>, Coordinate<i64>, Coordinate<i64>)> {
+/// Whether the press counts cannot be computed: the buttons move in the same direction or A
+/// does not move horizontally
+fn is_degenerate(a: &Coordinate<i64>, b: &Coordinate<i64>) -> bool {
+    a.x == 0 || a.x * b.y - b.x * a.y == 0
+}
+
+fn parse_input(input: &str) -> Result<Vec<Machine>, AocError> {
     input
         .trim()
         .split("\n\n")
         .map(|machine| {
             let mut machine = machine.trim().lines();
-            let (ax, ay) = machine
-                .next()
-                .unwrap()
-                .split_once(":")
-                .expect("The input is not correct")
-                .1
-                .split_once(",")
-                .expect("The input is not correct");
-
-            let (bx, by) = machine
-                .next()
-                .unwrap()
-                .split_once(":")
-                .expect("The input is not correct")
-                .1
-                .split_once(",")
-                .expect("The input is not correct");
-
-            let (px, py) = machine
-                .next()
-                .unwrap()
-                .split_once(":")
-                .expect("The input is not correct")
-                .1
-                .split_once(",")
-                .expect("The input is not correct");
-
-            (
-                Coordinate::new(
-                    ax.trim()[2..].parse().unwrap(),
-                    ay.trim()[2..].parse().unwrap(),
-                ),
-                Coordinate::new(
-                    bx.trim()[2..].parse().unwrap(),
-                    by.trim()[2..].parse().unwrap(),
-                ),
-                Coordinate::new(
-                    px.trim()[2..].parse().unwrap(),
-                    py.trim()[2..].parse().unwrap(),
-                ),
-            )
+            let mut next = || {
+                machine.next().map(parse_coordinate).unwrap_or_else(|| {
+                    Err(AocError::invalid_input(
+                        "a machine must have two buttons and a prize",
+                    ))
+                })
+            };
+
+            Ok((next()?, next()?, next()?))
         })
         .collect()
 }
 
+/// Parses the coordinate of a line such as `Button A: X+94, Y+34` or `Prize: X=8400, Y=5400`
+fn parse_coordinate(line: &str) -> Result<Coordinate<i64>, AocError> {
+    let invalid =
+        || AocError::invalid_input(format!("`{}` is not a button or a prize", line.trim()));
+
+    let (x, y) = line
+        .split_once(":")
+        .and_then(|(_, coordinate)| coordinate.split_once(","))
+        .ok_or_else(invalid)?;
+    let number =
+        |n: &str| -> Result<i64, AocError> { Ok(n.trim().get(2..).ok_or_else(invalid)?.parse()?) };
+
+    Ok(Coordinate::new(number(x)?, number(y)?))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -129,7 +144,27 @@ mod test {
             Button B: X+27, Y+71
             Prize: X=18641, Y=10279";
 
-        let (p1, _) = solve(input);
+        let (p1, _) = Day13::solve(input).unwrap();
         assert_eq!(p1, Solution::from(480_u64))
     }
+
+    #[test]
+    fn invalid_input() {
+        let input = "
+            Button A: X+94, Y+34
+            Button B: X+22, Y+67";
+        assert!(matches!(
+            Day13::solve(input),
+            Err(AocError::InvalidInput(_))
+        ));
+
+        let input = "
+            Button A: X+94, Y+34
+            Button B: X+22
+            Prize: X=8400, Y=5400";
+        assert!(matches!(
+            Day13::solve(input),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
 }
@@ -1,60 +1,66 @@
 use image::ImageBuffer;
-
-use std::{fs, sync::mpsc::channel};
-
-#[cfg(all(not(test), feature = "visualize"))]
 use owo_colors::OwoColorize;
-#[cfg(all(not(test), feature = "visualize"))]
-use std::{collections::HashMap, thread::sleep, time::Duration};
+use tracing::info;
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Write},
+    io,
+    time::Duration,
+};
 
-use crate::{Solution, SolutionPair};
+use crate::etc::Artifacts;
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let _ = fs::remove_dir_all("target/day14");
+pub struct Day14;
 
-    let mut robots = parse_input(input);
-    let grid: Grid = Grid::new(101, 103);
-    let seconds = 100;
+register_day!(Day14, 2024, 14, "Restroom Redoubt");
 
-    let mut robots_p1 = robots.clone();
-    for _ in 0..seconds {
-        for robot in robots_p1.iter_mut() {
-            *robot = robot.accelerate(grid.bounds());
-        }
-        #[cfg(all(not(test), feature = "visualize"))]
-        grid.print_robots(&robots_p1);
+impl Day for Day14 {
+    type Parsed = Vec<Robot>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        parse_input(input)
     }
 
-    let sol1: u64 = grid.safety_factor(&robots) as u64;
-    let sol2: String = String::from("Check the target folder for the images generated");
+    fn part1(robots: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let grid: Grid = Grid::new(101, 103);
+        let seconds = 100;
 
-    let (tx, rx) = channel();
+        let mut robots_p1 = robots.clone();
+        for _ in 0..seconds {
+            for robot in robots_p1.iter_mut() {
+                *robot = robot.accelerate(grid.bounds());
+            }
+            ctx.visualizer.frame(Duration::from_millis(5), |f| {
+                grid.draw_robots(f, &robots_p1)
+            });
+        }
 
-    ctrlc::set_handler(move || tx.send(()).expect("Could not send signal on channel."))
-        .expect("Error setting Ctrl-C handler");
+        let sol1: u64 = grid.safety_factor(robots) as u64;
 
-    println!("Generating images for part 2");
-    println!("Press Ctrl-C to stop generating...");
-    let mut seconds = 0;
-    loop {
-        for robot in robots.iter_mut() {
-            *robot = robot.accelerate(grid.bounds());
-        }
+        Ok(Solution::from(sol1))
+    }
 
-        seconds += 1;
-        grid.create_map_image(&robots, seconds);
+    fn part2(robots: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let grid: Grid = Grid::new(101, 103);
 
+        let (seconds, tree) = grid
+            .find_tree(robots, ctx)?
+            .ok_or_else(|| AocError::no_solution("the robots never stop overlapping"))?;
+        info!(seconds, "tree found");
 
-        // Stop generaing images if ctrl-c is recieved
-        if rx.try_recv().is_ok() {
-            break;
+        // The image is only written in a run, which has somewhere to put it
+        if ctx.artifacts.dir().is_some() {
+            grid.create_map_image(&tree, seconds, &ctx.artifacts)?;
         }
-    }
-    println!("\nElapsed: {}", seconds);
 
-    (Solution::from(sol1), Solution::from(sol2))
+        let sol2: u64 = seconds;
+
+        Ok(Solution::from(sol2))
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
@@ -90,9 +96,7 @@ impl Grid {
         (self.width, self.height)
     }
 
-    #[cfg(all(not(test), feature = "visualize"))]
-    fn print_robots(&self, robots: &Vec<Robot>) {
-        println!("{}[2J", 27 as char);
+    fn draw_robots(&self, f: &mut String, robots: &[Robot]) -> fmt::Result {
         let mut robots_map: HashMap<Coordinate<i64>, usize> = HashMap::with_capacity(robots.len());
 
         for robot in robots {
@@ -106,7 +110,7 @@ impl Grid {
                 for robot in robots {
                     if Coordinate::from((x as i64, y as i64)) == robot.position {
                         let robot_count = robots_map.get(&robot.position).unwrap_or(&1);
-                        print!("{:2}", robot_count.on_red());
+                        write!(f, "{:2}", robot_count.on_red())?;
                         is_robot = true;
                         break;
                     }
@@ -115,24 +119,54 @@ impl Grid {
                 if !is_robot {
                     if y % 2 == 0 {
                         if x % 2 == 0 {
-                            print!("{}", "  ".on_truecolor(60, 60, 60));
+                            write!(f, "{}", "  ".on_truecolor(60, 60, 60))?;
                         } else {
-                            print!("{}", "  ".on_truecolor(50, 50, 50));
+                            write!(f, "{}", "  ".on_truecolor(50, 50, 50))?;
                         }
                     } else if x % 2 != 0 {
-                        print!("{}", "  ".on_truecolor(60, 60, 60));
+                        write!(f, "{}", "  ".on_truecolor(60, 60, 60))?;
                     } else {
-                        print!("{}", "  ".on_truecolor(50, 50, 50));
+                        write!(f, "{}", "  ".on_truecolor(50, 50, 50))?;
                     }
                 }
             }
-            println!();
+            writeln!(f)?;
         }
-        println!("Safety factor: {}", self.safety_factor(robots));
-        sleep(Duration::from_millis(5));
+        writeln!(f, "Safety factor: {}", self.safety_factor(robots))
     }
 
-    fn create_map_image(&self, robots: &[Robot], seconds: u64) {
+    /// The first second at which no two robots share a position, which is when they draw the
+    /// tree, with the robots at that second. The robots are back where they started after
+    /// `width * height` seconds, so `None` if it did not happen by then
+    fn find_tree(
+        &self,
+        robots: &[Robot],
+        ctx: &Context,
+    ) -> Result<Option<(u64, Vec<Robot>)>, AocError> {
+        let mut robots = robots.to_vec();
+        let mut positions = HashSet::with_capacity(robots.len());
+
+        for seconds in 1..=(self.width * self.height) as u64 {
+            ctx.cancel.check()?;
+            for robot in robots.iter_mut() {
+                *robot = robot.accelerate(self.bounds());
+            }
+
+            positions.clear();
+            if robots.iter().all(|robot| positions.insert(robot.position)) {
+                return Ok(Some((seconds, robots)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn create_map_image(
+        &self,
+        robots: &[Robot],
+        seconds: u64,
+        artifacts: &Artifacts,
+    ) -> Result<(), AocError> {
         // Create an image
         let mut imgbuf = ImageBuffer::new(self.width as u32, self.height as u32);
 
@@ -155,12 +189,11 @@ impl Grid {
             }
         }
 
-        // Create the empty directory to store all the generated images
-        fs::create_dir_all("target/day14").expect("Unable to create folder to generate images");
-
         imgbuf
-            .save(format!("target/day14/{}.png", seconds))
-            .expect("The target folder doesn't exist");
+            .save(artifacts.path(format!("{}.png", seconds))?)
+            .map_err(io::Error::other)?;
+
+        Ok(())
     }
 
     fn robots_in_quadrant(&self, quadrant: Quadrant, robots: &[Robot]) -> usize {
@@ -222,7 +255,7 @@ enum Quadrant {
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
-struct Robot {
+pub struct Robot {
     position: Coordinate<i64>,
     velocity: Coordinate<i64>,
 }
@@ -255,28 +288,35 @@ impl Robot {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Robot> {
+fn parse_input(input: &str) -> Result<Vec<Robot>, AocError> {
     input
         .trim()
         .lines()
         .map(|l| {
-            let (position, velocity) = l.trim().split_once(' ').unwrap();
-            let position: Coordinate<i64> = position[2..]
+            let (position, velocity) = l
                 .trim()
-                .split_once(',')
-                .map(|(x, y)| Coordinate::new(x.parse().unwrap(), y.parse().unwrap()))
-                .unwrap();
-            let velocity: Coordinate<i64> = velocity[2..]
-                .trim()
-                .split_once(',')
-                .map(|(x, y)| Coordinate::new(x.parse().unwrap(), y.parse().unwrap()))
-                .unwrap();
+                .split_once(' ')
+                .ok_or_else(|| AocError::invalid_input(format!("`{}` is not a robot", l.trim())))?;
 
-            Robot::new(position, velocity)
+            Ok(Robot::new(
+                parse_coordinate(position)?,
+                parse_coordinate(velocity)?,
+            ))
         })
         .collect()
 }
 
+/// Parses a coordinate such as `p=0,4` or `v=-3,3`
+fn parse_coordinate(text: &str) -> Result<Coordinate<i64>, AocError> {
+    let (x, y) = text
+        .trim()
+        .get(2..)
+        .and_then(|coordinate| coordinate.split_once(','))
+        .ok_or_else(|| AocError::invalid_input(format!("`{text}` is not a coordinate")))?;
+
+    Ok(Coordinate::new(x.parse()?, y.parse()?))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -310,4 +350,45 @@ mod test {
         let robot = robot.accelerate(grid.bounds());
         assert_eq!(Robot::new((2, 2), robot.velocity), robot);
     }
+
+    #[test]
+    fn find_tree() {
+        let grid: Grid = Grid::new(3, 3);
+        let ctx = Context::default();
+
+        let robots = [Robot::new((0, 0), (1, 0)), Robot::new((0, 0), (2, 0))];
+        let (seconds, tree) = grid.find_tree(&robots, &ctx).unwrap().unwrap();
+        assert_eq!(seconds, 1);
+        assert_eq!(
+            tree,
+            [Robot::new((1, 0), (1, 0)), Robot::new((2, 0), (2, 0))]
+        );
+
+        // Robots moving together never separate
+        let robots = [Robot::new((0, 0), (1, 1)), Robot::new((0, 0), (1, 1))];
+        assert_eq!(grid.find_tree(&robots, &ctx).unwrap(), None);
+
+        let cancelled = Context::default();
+        cancelled.cancel.cancel();
+        assert!(matches!(
+            grid.find_tree(&robots, &cancelled),
+            Err(AocError::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn parse_robots() {
+        assert_eq!(
+            Day14::parse("p=0,4 v=3,-3\np=6,3 v=-1,-3").unwrap(),
+            vec![Robot::new((0, 4), (3, -3)), Robot::new((6, 3), (-1, -3))]
+        );
+        assert!(matches!(
+            Day14::parse("p=0,4"),
+            Err(AocError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            Day14::parse("p=0,a v=3,-3"),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
 }
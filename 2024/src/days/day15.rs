@@ -1,41 +1,71 @@
 use owo_colors::OwoColorize;
 use std::{
     collections::HashSet,
-    fmt::Display,
+    fmt::{Display, Write},
     ops::{Add, AddAssign, Mul},
+    time::Duration,
 };
 
-#[cfg(all(not(test), feature = "visualize"))]
-use std::{thread::sleep, time::Duration};
+use tracing::trace;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::Visualizer;
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (mut small_grid, directions) = parse_input(input).unwrap_or_else(|e| panic!("{e}"));
-    let mut wide_grid = small_grid.to_wide();
+pub struct Day15;
 
-    small_grid.run_robot(&directions);
-    let sol1: u64 = small_grid.calculate_gps();
+register_day!(Day15, 2024, 15, "Warehouse Woes");
 
-    wide_grid.run_robot(&directions);
-    let sol2: u64 = wide_grid.calculate_gps();
+impl Day for Day15 {
+    /// The warehouse with the small boxes and the moves of the robot
+    type Parsed = (Grid<SmallBox>, Vec<Direction>);
 
-    (Solution::from(sol1), Solution::from(sol2))
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        parse_input(input)
+    }
+
+    fn part1((small_grid, directions): &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let mut small_grid = small_grid.clone();
+
+        small_grid.run_robot(directions, &ctx.visualizer);
+        let sol1: u64 = small_grid.calculate_gps();
+
+        Ok(Solution::from(sol1))
+    }
+
+    fn part2((small_grid, directions): &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let mut wide_grid = small_grid.to_wide();
+
+        wide_grid.run_robot(directions, &ctx.visualizer);
+        let sol2: u64 = wide_grid.calculate_gps();
+
+        Ok(Solution::from(sol2))
+    }
 }
 
-fn parse_input(input: &str) -> Result<(Grid<SmallBox>, Vec<Direction>), String> {
+fn parse_input(input: &str) -> Result<(Grid<SmallBox>, Vec<Direction>), AocError> {
     let (grid, moves) = match input.trim().split_once("\n\n") {
         Some(val) => val,
-        None => return Err(String::from("Wrong input")),
+        None => {
+            return Err(AocError::invalid_input(
+                "the warehouse and the moves must be separated by an empty line",
+            ))
+        }
     };
 
     let mut robot: Option<Coordinate<i64>> = None;
     let mut boxes: Vec<SmallBox> = Vec::new();
     let mut walls: Vec<Coordinate<i64>> = Vec::new();
     let height = grid.trim().lines().count();
-    let width = grid.trim().lines().next().unwrap().trim().chars().count();
+    let width = grid
+        .trim()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .chars()
+        .count();
 
     for (y, line) in grid.trim().lines().enumerate() {
         for (x, cell) in line.trim().chars().enumerate() {
@@ -49,11 +79,10 @@ fn parse_input(input: &str) -> Result<(Grid<SmallBox>, Vec<Direction>), String>
         }
     }
 
-    if robot.is_none() {
-        return Err(String::from("No robot in input"));
-    }
+    let robot =
+        robot.ok_or_else(|| AocError::invalid_input("there is no robot in the warehouse"))?;
 
-    let grid = Grid::new(robot.unwrap(), boxes, walls, width, height);
+    let grid = Grid::new(robot, boxes, walls, width, height);
 
     let moves: Vec<Direction> = moves
         .trim()
@@ -68,7 +97,7 @@ fn parse_input(input: &str) -> Result<(Grid<SmallBox>, Vec<Direction>), String>
 // Grid
 //
 #[derive(Clone)]
-struct Grid<T> {
+pub struct Grid<T> {
     robot: Coordinate<i64>,
     boxes: Vec<T>,
     walls: Vec<Coordinate<i64>>,
@@ -162,15 +191,16 @@ where
         Some(direction.delta().into())
     }
 
-    fn run_robot(&mut self, directions: &[Direction]) {
+    fn run_robot(&mut self, directions: &[Direction], visualizer: &Visualizer) {
         for direction in directions {
             self.move_robot(direction);
-            #[cfg(all(not(test), feature = "visualize"))]
-            {
-                println!("{}[2J", 27 as char);
-                println!("{self}");
-                sleep(Duration::from_millis(4));
-            }
+            trace!(
+                ?direction,
+                x = self.robot.x,
+                y = self.robot.y,
+                "robot moved"
+            );
+            visualizer.frame(Duration::from_millis(4), |f| writeln!(f, "{self}"));
         }
     }
 
@@ -214,39 +244,29 @@ impl<T: FoodBox> Display for Grid<T> {
         let box_char = T::display_char();
         let robot_char = '@';
 
+        // A line of the input may be longer than the first one, what is beyond is not drawn
+        let mut draw = |position: Coordinate<i64>, c: char| {
+            let cell = usize::try_from(position.y)
+                .ok()
+                .zip(usize::try_from(position.x).ok())
+                .and_then(|(y, x)| grid.get_mut(y)?.get_mut(x));
+            if let Some(cell) = cell {
+                *cell = c;
+            }
+        };
+
         for b in &self.boxes {
-            let cell = grid
-                .get_mut(b.position().y as usize)
-                .unwrap()
-                .get_mut(b.position().x as usize)
-                .unwrap();
-            *cell = box_char;
-
-            if box_char == WideBox::display_char() && b.position().x as usize + 1 < self.width {
-                let right_cell = grid
-                    .get_mut(b.position().y as usize)
-                    .unwrap()
-                    .get_mut(b.position().x as usize + 1)
-                    .unwrap();
-                *right_cell = ']';
+            draw(b.position(), box_char);
+            if box_char == WideBox::display_char() {
+                draw(b.position() + Direction::Right.delta().into(), ']');
             }
         }
 
-        for w in &self.walls {
-            let cell = grid
-                .get_mut(w.y as usize)
-                .unwrap()
-                .get_mut(w.x as usize)
-                .unwrap();
-            *cell = wall_char;
+        for &w in &self.walls {
+            draw(w, wall_char);
         }
 
-        let cell = grid
-            .get_mut(self.robot.y as usize)
-            .unwrap()
-            .get_mut(self.robot.x as usize)
-            .unwrap();
-        *cell = robot_char;
+        draw(self.robot, robot_char);
 
         for line in grid {
             for cell in line {
@@ -270,7 +290,7 @@ impl<T: FoodBox> Display for Grid<T> {
 //
 // FoodBox
 //
-trait FoodBox {
+pub trait FoodBox {
     fn position(&self) -> Coordinate<i64>;
 
     fn display_char() -> char
@@ -296,7 +316,7 @@ trait FoodBox {
 // SmallBox
 //
 #[derive(Clone, Copy, PartialEq, Debug, Default, Eq, Hash)]
-struct SmallBox {
+pub struct SmallBox {
     position: Coordinate<i64>,
 }
 
@@ -425,7 +445,7 @@ impl AddAssign<Coordinate<i64>> for WideBox {
 // Coordinate
 //
 #[derive(Debug, PartialEq, Clone, Copy, Hash, Eq, Default)]
-struct Coordinate<T> {
+pub struct Coordinate<T> {
     pub x: T,
     pub y: T,
 }
@@ -471,7 +491,7 @@ where
 // Direction
 //
 #[derive(Debug)]
-enum Direction {
+pub enum Direction {
     Up,
     Down,
     Left,
@@ -509,6 +529,24 @@ impl Direction {
 mod test {
     use super::*;
 
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            Day15::parse("#..O.#\n#....#"),
+            Err(AocError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            Day15::parse("#..O.#\n\n<^^>"),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn draw_lines_longer_than_the_first() {
+        let (grid, _) = parse_input("##\n#.O@#\n\n<").unwrap();
+        assert_eq!(grid.to_string().lines().count(), 2);
+    }
+
     #[test]
     fn coordinate_sum() {
         assert_eq!(
@@ -1,29 +1,45 @@
 use std::{
-    fmt::Display,
+    fmt::{self, Display, Write},
     ops::{Add, AddAssign, Mul},
-    thread::sleep,
     time::Duration,
 };
 
 use owo_colors::OwoColorize;
+use tracing::debug;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::Visualizer;
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let grid = parse_input(input);
-    let shortest_path = grid.a_star(grid.start, grid.end);
+pub struct Day16;
 
-    let mut reindeer = Reindeer::new(grid.start);
+register_day!(Day16, 2024, 16, "Reindeer Maze", PartOne);
 
-    let sol1: u64 = reindeer.run(&shortest_path, &grid);
-    let sol2: u64 = 0;
+impl Day for Day16 {
+    type Parsed = Grid;
 
-    (Solution::from(sol1), Solution::from(sol2))
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(grid: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        let visualizer = &ctx.visualizer;
+        let shortest_path = grid.a_star(grid.start, grid.end, visualizer)?;
+
+        let mut reindeer = Reindeer::new(grid.start);
+
+        let sol1: u64 = reindeer.run(&shortest_path, grid, visualizer);
+
+        Ok(Solution::from(sol1))
+    }
+
+    fn part2(_grid: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        Err(AocError::no_solution("part 2 is not solved yet"))
+    }
 }
 
-fn parse_input(input: &str) -> Grid {
+fn parse_input(input: &str) -> Result<Grid, AocError> {
     Grid::new(
         input
             .trim()
@@ -116,14 +132,14 @@ where
 //
 // Grid
 //
-struct Grid {
+pub struct Grid {
     cells: Vec<Vec<Tile>>,
     start: Coordinate<i64>,
     end: Coordinate<i64>,
 }
 
 impl Grid {
-    fn new(cells: Vec<Vec<Tile>>) -> Self {
+    fn new(cells: Vec<Vec<Tile>>) -> Result<Self, AocError> {
         let mut start = None;
         let mut end = None;
 
@@ -141,15 +157,24 @@ impl Grid {
             }
         }
 
-        Self {
+        Ok(Self {
             cells,
-            start: start.expect("No start found").into(),
-            end: end.expect("No end found").into(),
-        }
+            start: start
+                .ok_or_else(|| AocError::invalid_input("the maze has no start"))?
+                .into(),
+            end: end
+                .ok_or_else(|| AocError::invalid_input("the maze has no end"))?
+                .into(),
+        })
     }
 
-    fn print_a_star(&self, open: &[Path], close: &[Path], path: Option<&[Coordinate<i64>]>) {
-        print!("{}[2J", 27 as char);
+    fn draw_a_star(
+        &self,
+        f: &mut String,
+        open: &[Path],
+        close: &[Path],
+        path: Option<&[Coordinate<i64>]>,
+    ) -> fmt::Result {
         for (y, line) in self.cells.iter().enumerate() {
             'next_cell: for (x, cell) in line.iter().enumerate() {
                 if let Some(path) = path {
@@ -158,7 +183,7 @@ impl Grid {
                             && *cell != Tile::Start
                             && *cell != Tile::End
                         {
-                            print!("{}", "  ".on_blue());
+                            write!(f, "{}", "  ".on_blue())?;
                             continue 'next_cell;
                         }
                     }
@@ -169,7 +194,7 @@ impl Grid {
                         && *cell != Tile::Start
                         && *cell != Tile::End
                     {
-                        print!("{}", "  ".on_bright_magenta());
+                        write!(f, "{}", "  ".on_bright_magenta())?;
                         continue 'next_cell;
                     }
                 }
@@ -179,24 +204,32 @@ impl Grid {
                         && *cell != Tile::Start
                         && *cell != Tile::End
                     {
-                        print!("{}", "  ".on_purple());
+                        write!(f, "{}", "  ".on_purple())?;
                         continue 'next_cell;
                     }
                 }
 
-                print!("{cell}");
+                write!(f, "{cell}")?;
             }
-            println!();
+            writeln!(f)?;
         }
-        sleep(Duration::from_millis(10));
+
+        Ok(())
     }
 
-    fn a_star(&self, start_pos: Coordinate<i64>, end_pos: Coordinate<i64>) -> Vec<Coordinate<i64>> {
+    fn a_star(
+        &self,
+        start_pos: Coordinate<i64>,
+        end_pos: Coordinate<i64>,
+        visualizer: &Visualizer,
+    ) -> Result<Vec<Coordinate<i64>>, AocError> {
         let mut open = vec![Path::new(start_pos)]; // Nodes to evaluate
         let mut close = vec![]; // Nodes already evaluated
 
         loop {
-            self.print_a_star(&open, &close, None);
+            visualizer.frame(Duration::from_millis(10), |f| {
+                self.draw_a_star(f, &open, &close, None)
+            });
             let min_idx = open
                 .iter()
                 .position(|c| {
@@ -207,16 +240,23 @@ impl Grid {
                             .min()
                             .unwrap_or_default()
                 })
-                .unwrap();
+                .ok_or_else(|| AocError::no_solution("the end of the maze cannot be reached"))?;
 
             let current = open.remove(min_idx);
             close.push(current.clone());
 
             if current.position == end_pos {
                 let path = self.retrace_path(close.first().unwrap(), close.last().unwrap());
-                self.print_a_star(&open, &close, Some(&path));
-                sleep(Duration::from_secs(1));
-                return path;
+                debug!(
+                    steps = path.len(),
+                    evaluated = close.len(),
+                    "shortest path found"
+                );
+                // The path stays on screen before the reindeer follows it
+                visualizer.frame(Duration::from_secs(1), |f| {
+                    self.draw_a_star(f, &open, &close, Some(&path))
+                });
+                return Ok(path);
             }
 
             for mut neighbour in self.neighbours(current.position) {
@@ -323,12 +363,16 @@ impl Grid {
         self.cells.len()
     }
 
-    fn print_reindeer(&self, path: &[Coordinate<i64>], reindeer: &Reindeer) {
-        print!("{}[2J", 27 as char);
+    fn draw_reindeer(
+        &self,
+        f: &mut String,
+        path: &[Coordinate<i64>],
+        reindeer: &Reindeer,
+    ) -> fmt::Result {
         for (y, line) in self.cells.iter().enumerate() {
             'next_cell: for (x, cell) in line.iter().enumerate() {
                 if reindeer.position == Coordinate::new(x as i64, y as i64) {
-                    print!("{}{}", " ".on_yellow(), reindeer.direction.on_yellow());
+                    write!(f, "{}{}", " ".on_yellow(), reindeer.direction.on_yellow())?;
                     continue 'next_cell;
                 }
 
@@ -337,16 +381,17 @@ impl Grid {
                         && *cell != Tile::Start
                         && *cell != Tile::End
                     {
-                        print!("{}", "  ".on_blue());
+                        write!(f, "{}", "  ".on_blue())?;
                         continue 'next_cell;
                     }
                 }
 
-                print!("{cell}");
+                write!(f, "{cell}")?;
             }
-            println!();
+            writeln!(f)?;
         }
-        sleep(Duration::from_millis(100));
+
+        Ok(())
     }
 }
 
@@ -445,7 +490,7 @@ impl Reindeer {
         self.position += direction.delta();
     }
 
-    fn run(&mut self, path: &[Coordinate<i64>], grid: &Grid) -> u64 {
+    fn run(&mut self, path: &[Coordinate<i64>], grid: &Grid, visualizer: &Visualizer) -> u64 {
         let mut turns = 0;
         let mut moves = 0;
 
@@ -457,7 +502,9 @@ impl Reindeer {
         ];
 
         for p in path.iter().rev() {
-            grid.print_reindeer(path, self);
+            visualizer.frame(Duration::from_millis(100), |f| {
+                grid.draw_reindeer(f, path, self)
+            });
             for direction in directions {
                 if *p == self.position + direction.delta() {
                     while self.direction != direction {
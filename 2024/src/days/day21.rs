@@ -1,11 +1,29 @@
-use crate::{Solution, SolutionPair};
+use crate::{register_day, AocError, Context, Day, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    // Your solution here...
-    let sol1: u64 = 0;
-    let sol2: u64 = 0;
+pub struct Day21;
 
-    (Solution::from(sol1), Solution::from(sol2))
+register_day!(Day21, 2024, 21, "Keypad Conundrum", Stub);
+
+impl Day for Day21 {
+    type Parsed = ();
+
+    fn parse(_input: &str) -> Result<Self::Parsed, AocError> {
+        Ok(())
+    }
+
+    fn part1(_parsed: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        // Your solution here...
+        let sol1: u64 = 0;
+
+        Ok(Solution::from(sol1))
+    }
+
+    fn part2(_parsed: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        // Your solution here...
+        let sol2: u64 = 0;
+
+        Ok(Solution::from(sol2))
+    }
 }
@@ -0,0 +1,270 @@
+use std::{
+    cell::Cell,
+    fmt::{self, Display, Formatter},
+};
+
+use serde::Serialize;
+
+/// The memory allocated by a stage of a day, only counted with the `memory` feature. The
+/// allocations are counted in the thread that makes them, so each day is measured on its own
+/// even when the days run in parallel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    /// Bytes requested, a reallocation counts as a new allocation of the new size
+    pub bytes: u64,
+    /// Number of allocations and reallocations
+    pub count: u64,
+    /// Most bytes in use at once, on top of the ones already in use when the stage started
+    pub peak: u64,
+    /// Bytes still in use once the stage is done, like the parsed input that the parts run on
+    pub retained: u64,
+}
+
+impl AllocStats {
+    /// The allocations of several stages run one after the other. Each stage peaks on top of
+    /// what the previous ones retained
+    pub fn total(stages: impl IntoIterator<Item = AllocStats>) -> Self {
+        stages
+            .into_iter()
+            .fold(AllocStats::default(), |total, stage| AllocStats {
+                bytes: total.bytes + stage.bytes,
+                count: total.count + stage.count,
+                peak: total.peak.max(total.retained + stage.peak),
+                retained: total.retained + stage.retained,
+            })
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} in {} allocations, peak {}",
+            Bytes(self.bytes),
+            self.count,
+            Bytes(self.peak)
+        )
+    }
+}
+
+/// A size in bytes with a binary unit
+struct Bytes(u64);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.;
+        let mut unit = 0;
+        while size >= 1024. && unit + 1 < UNITS.len() {
+            size /= 1024.;
+            unit += 1;
+        }
+        write!(f, "{size:.1} {}", UNITS[unit])
+    }
+}
+
+/// What the current thread allocated since it started. There is nothing to drop so that the
+/// allocator can use it at any time
+struct Counters {
+    bytes: Cell<u64>,
+    count: Cell<u64>,
+    /// Bytes in use, negative when the thread frees memory allocated by another one
+    current: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            bytes: Cell::new(0),
+            count: Cell::new(0),
+            current: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+/// The system allocator, counting the allocations of each thread
+#[cfg(feature = "memory")]
+struct Counting;
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[cfg(feature = "memory")]
+unsafe impl std::alloc::GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let ptr = std::alloc::System.alloc(layout);
+        if !ptr.is_null() {
+            count(layout.size(), layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let ptr = std::alloc::System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(layout.size(), layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        std::alloc::System.dealloc(ptr, layout);
+        count(0, -(layout.size() as i64));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = std::alloc::System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            count(new_size, new_size as i64 - layout.size() as i64);
+        }
+        new_ptr
+    }
+}
+
+/// Adds an allocation of `allocated` bytes, none when it is 0, and changes the bytes in use
+#[cfg(feature = "memory")]
+fn count(allocated: usize, change: i64) {
+    // Fails only while the thread is being torn down
+    let _ = COUNTERS.try_with(|counters| {
+        if allocated > 0 {
+            counters.bytes.set(counters.bytes.get() + allocated as u64);
+            counters.count.set(counters.count.get() + 1);
+        }
+        let current = counters.current.get() + change;
+        counters.current.set(current);
+        counters.peak.set(counters.peak.get().max(current));
+    });
+}
+
+/// Runs `f` and returns what it allocated in this thread, `None` without the `memory` feature
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    if !cfg!(feature = "memory") {
+        return (f(), None);
+    }
+
+    let (bytes, count, current) = COUNTERS.with(|counters| {
+        counters.peak.set(counters.current.get());
+        (
+            counters.bytes.get(),
+            counters.count.get(),
+            counters.current.get(),
+        )
+    });
+
+    let result = f();
+
+    let stats = COUNTERS.with(|counters| AllocStats {
+        bytes: counters.bytes.get() - bytes,
+        count: counters.count.get() - count,
+        peak: (counters.peak.get() - current).max(0) as u64,
+        retained: (counters.current.get() - current).max(0) as u64,
+    });
+
+    (result, Some(stats))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn measure_allocations() {
+        let (len, stats) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(1024);
+            v.push(1);
+            drop(v);
+            let v: Vec<u8> = vec![0; 100];
+            v.len()
+        });
+        assert_eq!(len, 100);
+
+        match cfg!(feature = "memory") {
+            true => assert_eq!(
+                stats,
+                Some(AllocStats {
+                    bytes: 8 * 1024 + 100,
+                    count: 2,
+                    peak: 8 * 1024,
+                    retained: 0,
+                })
+            ),
+            false => assert_eq!(stats, None),
+        }
+    }
+
+    #[test]
+    fn total() {
+        let stats = AllocStats::total([
+            AllocStats {
+                bytes: 10,
+                count: 1,
+                peak: 10,
+                retained: 0,
+            },
+            AllocStats {
+                bytes: 30,
+                count: 3,
+                peak: 20,
+                retained: 5,
+            },
+        ]);
+        assert_eq!(
+            stats,
+            AllocStats {
+                bytes: 40,
+                count: 4,
+                peak: 20,
+                retained: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn retained_by_parse() {
+        // The parsed input stays alive while the part allocates on top of it
+        let (parsed, parse) = measure(|| vec![0_u8; 4096]);
+        let (_, part) = measure(|| vec![0_u8; 100].len() + parsed.len());
+
+        // Only measured with the `memory` feature
+        if let (Some(parse), Some(part)) = (parse, part) {
+            assert_eq!(parse.retained, 4096);
+            assert_eq!(part.retained, 0);
+            assert_eq!(AllocStats::total([parse, part]).peak, 4096 + 100);
+        }
+
+        let total = AllocStats::total([
+            AllocStats {
+                bytes: 4096,
+                count: 1,
+                peak: 4096,
+                retained: 4096,
+            },
+            AllocStats {
+                bytes: 100,
+                count: 1,
+                peak: 100,
+                retained: 0,
+            },
+        ]);
+        assert_eq!(total.peak, 4096 + 100);
+        assert_eq!(total.retained, 4096);
+    }
+
+    #[test]
+    fn display() {
+        let stats = AllocStats {
+            bytes: 3 * 1024 * 1024 / 2,
+            count: 12,
+            peak: 512,
+            retained: 0,
+        };
+        assert_eq!(stats.to_string(), "1.5 MiB in 12 allocations, peak 512 B");
+    }
+}
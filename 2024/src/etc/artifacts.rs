@@ -0,0 +1,279 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::etc::AocError;
+
+/// The default directory of the runs, one `<run id>/YYYY/dayNN` directory per day that produced
+/// files
+pub fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("target/aoc-out")
+}
+
+/// Which runs are kept in the output directory once a new one ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retention {
+    All,
+    /// The most recent ones, the new run included
+    Last(usize),
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Retention::Last(10)
+    }
+}
+
+impl FromStr for Retention {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Retention::All),
+            _ => match s.parse::<usize>() {
+                Ok(0) | Err(_) => Err(format!(
+                    "`{s}` is neither `all` nor a positive number of runs"
+                )),
+                Ok(n) => Ok(Retention::Last(n)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Retention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Retention::All => write!(f, "all"),
+            Retention::Last(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+/// A run of the runner in the output directory. Its directory is named after the second it
+/// started and the process, so that the runs sort in order and concurrent ones never share it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactRun {
+    pub out_dir: PathBuf,
+    pub id: String,
+}
+
+impl ArtifactRun {
+    /// A run starting now, nothing is created until a day writes a file
+    pub fn new(out_dir: &Path) -> Self {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        ArtifactRun {
+            out_dir: out_dir.to_owned(),
+            id: format!("{started}-{}", std::process::id()),
+        }
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        self.out_dir.join(&self.id)
+    }
+
+    /// Where a day writes its files in this run
+    pub fn day(&self, year: u16, day: u8) -> Artifacts {
+        Artifacts {
+            dir: Some(
+                self.dir()
+                    .join(year.to_string())
+                    .join(format!("day{:02}", day)),
+            ),
+        }
+    }
+
+    /// Removes the oldest runs of the output directory that are not kept, this one counted if it
+    /// produced anything. The other directories are left alone. Returns the removed runs
+    pub fn clean(&self, retention: Retention) -> io::Result<Vec<PathBuf>> {
+        let keep = match retention {
+            Retention::All => return Ok(vec![]),
+            Retention::Last(n) => n,
+        };
+
+        let mut runs = vec![];
+        let entries = match fs::read_dir(&self.out_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(started) = run_start(&name) {
+                if entry.file_type()?.is_dir() {
+                    runs.push((started, name, entry.path()));
+                }
+            }
+        }
+        runs.sort();
+
+        let removed: Vec<_> = runs
+            .into_iter()
+            .rev()
+            .skip(keep)
+            .map(|(_, _, path)| path)
+            .collect();
+        for path in &removed {
+            fs::remove_dir_all(path)?;
+        }
+
+        Ok(removed)
+    }
+}
+
+/// The second a run started, from the name of its directory
+fn run_start(id: &str) -> Option<u64> {
+    let (started, pid) = id.split_once('-')?;
+    pid.parse::<u32>().ok()?;
+    started.parse().ok()
+}
+
+/// Where a solver writes the files it produces. The default one is outside of any run and
+/// refuses to write
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Artifacts {
+    dir: Option<PathBuf>,
+}
+
+impl Artifacts {
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// The path of a file named `name` in the directory of the day, which is created if needed
+    pub fn path(&self, name: impl AsRef<Path>) -> Result<PathBuf, AocError> {
+        let dir = self.dir.as_ref().ok_or_else(|| {
+            io::Error::other("files can only be written in a run, there is no output directory")
+        })?;
+        fs::create_dir_all(dir)?;
+
+        Ok(dir.join(name))
+    }
+
+    /// Removes the files of a previous solve of the day in the same run
+    pub fn clear(&self) -> io::Result<()> {
+        match &self.dir {
+            Some(dir) => match fs::remove_dir_all(dir) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// The files produced so far, sorted, the ones of subdirectories included
+    pub fn files(&self) -> Vec<PathBuf> {
+        fn walk(dir: &Path, files: &mut Vec<PathBuf>) {
+            for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+                match entry.file_type() {
+                    Ok(file_type) if file_type.is_dir() => walk(&entry.path(), files),
+                    Ok(_) => files.push(entry.path()),
+                    Err(_) => {}
+                }
+            }
+        }
+
+        let mut files = vec![];
+        if let Some(dir) = &self.dir {
+            walk(dir, &mut files);
+        }
+        files.sort();
+        files
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::etc::test_util::temp_dir;
+
+    #[test]
+    fn retention() {
+        assert_eq!("all".parse(), Ok(Retention::All));
+        assert_eq!("3".parse(), Ok(Retention::Last(3)));
+        assert!("0".parse::<Retention>().is_err());
+        assert!("last".parse::<Retention>().is_err());
+        assert_eq!(Retention::default().to_string(), "10");
+    }
+
+    #[test]
+    fn outside_of_a_run() {
+        let artifacts = Artifacts::default();
+        assert_eq!(artifacts.dir(), None);
+        assert!(artifacts.path("1.png").is_err());
+        assert!(artifacts.files().is_empty());
+    }
+
+    #[test]
+    fn files_of_a_day() {
+        let out_dir = temp_dir("artifacts");
+        let run = ArtifactRun::new(&out_dir);
+        let artifacts = run.day(2024, 14);
+        assert_eq!(
+            artifacts.dir(),
+            Some(run.dir().join("2024/day14").as_path())
+        );
+        assert!(!run.dir().exists());
+
+        for name in ["2.png", "10.png", "frames/1.txt"] {
+            let path = artifacts.path(name).unwrap();
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let day_dir = artifacts.dir().unwrap();
+        assert_eq!(
+            artifacts.files(),
+            [
+                day_dir.join("10.png"),
+                day_dir.join("2.png"),
+                day_dir.join("frames/1.txt")
+            ]
+        );
+
+        artifacts.clear().unwrap();
+        assert!(artifacts.files().is_empty());
+        artifacts.clear().unwrap();
+
+        fs::remove_dir_all(out_dir).unwrap();
+    }
+
+    #[test]
+    fn clean_old_runs() {
+        let out_dir = temp_dir("runs");
+        for id in ["900-1", "1000-2", "1000-1", "20-7", "notes", "1100-x"] {
+            fs::create_dir_all(out_dir.join(id)).unwrap();
+        }
+        fs::write(out_dir.join("30-1"), "").unwrap();
+        let run = ArtifactRun {
+            out_dir: out_dir.clone(),
+            id: String::from("1000-2"),
+        };
+
+        assert_eq!(run.clean(Retention::All).unwrap(), Vec::<PathBuf>::new());
+        assert_eq!(
+            run.clean(Retention::Last(2)).unwrap(),
+            [out_dir.join("900-1"), out_dir.join("20-7")]
+        );
+
+        let mut left: Vec<_> = fs::read_dir(&out_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["1000-1", "1000-2", "1100-x", "30-1", "notes"]);
+
+        fs::remove_dir_all(&out_dir).unwrap();
+        assert_eq!(
+            run.clean(Retention::Last(1)).unwrap(),
+            Vec::<PathBuf>::new()
+        );
+    }
+}
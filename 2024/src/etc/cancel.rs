@@ -0,0 +1,261 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex, Weak,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::etc::{AocError, Context, DayRun, DayRunner, Part};
+
+/// The tokens of the days that are running, cancelled all at once by Ctrl-C
+static ACTIVE: Mutex<Vec<Weak<AtomicBool>>> = Mutex::new(Vec::new());
+
+/// Tells a long running solver that it should stop, either because it ran out of time or
+/// because Ctrl-C was pressed
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        CancelToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fails with [`AocError::Cancelled`] if the solver should stop
+    pub fn check(&self) -> Result<(), AocError> {
+        match self.is_cancelled() {
+            true => Err(AocError::Cancelled),
+            false => Ok(()),
+        }
+    }
+
+    /// Runs `f` while Ctrl-C cancels this token
+    pub fn with_ctrlc<R>(&self, f: impl FnOnce() -> R) -> R {
+        /// Stops cancelling the token even if `f` panics
+        struct Unregister(Weak<AtomicBool>);
+
+        impl Drop for Unregister {
+            fn drop(&mut self) {
+                if let Ok(mut active) = ACTIVE.lock() {
+                    active.retain(|token| token.strong_count() > 0 && !token.ptr_eq(&self.0));
+                }
+            }
+        }
+
+        let weak = Arc::downgrade(&self.cancelled);
+        if let Ok(mut active) = ACTIVE.lock() {
+            active.push(weak.clone());
+        }
+        let _unregister = Unregister(weak);
+
+        f()
+    }
+}
+
+/// Makes Ctrl-C cancel the days that are running, or exit when there is none left to cancel.
+/// Can only be called once
+pub fn handle_ctrlc() -> Result<(), String> {
+    ctrlc::set_handler(|| {
+        let mut cancelled = false;
+        if let Ok(active) = ACTIVE.lock() {
+            for token in active.iter().filter_map(Weak::upgrade) {
+                cancelled |= !token.swap(true, Ordering::Relaxed);
+            }
+        }
+
+        if !cancelled {
+            process::exit(130);
+        }
+    })
+    .map_err(|e| format!("Could not handle Ctrl-C: {e}"))
+}
+
+/// Runs a day with its context, cancelling its token and giving up after `timeout` if any. Ctrl-C
+/// cancels it too. A solver that does not check the token is left running in the background
+/// once it timed out. The solver logs in the span of the calling thread, and a panic of the
+/// solver is returned as [`AocError::Panicked`] so that the other days still run
+pub fn run_with_timeout(
+    func: DayRunner,
+    input: &str,
+    part: Option<Part>,
+    timeout: Option<Duration>,
+    ctx: &Context,
+) -> Result<DayRun, AocError> {
+    let Some(timeout) = timeout else {
+        return panic::catch_unwind(AssertUnwindSafe(|| {
+            ctx.cancel.with_ctrlc(|| func(input, part, ctx))
+        }))
+        .unwrap_or_else(|panic| Err(AocError::Panicked(panic_message(panic))));
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_owned();
+    let solver_ctx = ctx.clone();
+    let span = tracing::Span::current();
+    let solver = thread::spawn(move || {
+        let _span = span.entered();
+        let day_run = solver_ctx
+            .cancel
+            .with_ctrlc(|| func(&input, part, &solver_ctx));
+        // Releases the visualization before the runner gets the result, so that its file is
+        // complete once the runner drops its own context
+        drop(solver_ctx);
+        // The runner stopped waiting if the day timed out
+        let _ = sender.send(day_run);
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(day_run) => day_run,
+        Err(RecvTimeoutError::Timeout) => {
+            ctx.cancel.cancel();
+            Err(AocError::TimedOut(timeout))
+        }
+        // The solver panicked, which must not be mistaken for a timeout
+        Err(RecvTimeoutError::Disconnected) => match solver.join() {
+            Err(panic) => Err(AocError::Panicked(panic_message(panic))),
+            Ok(()) => unreachable!("the solver always sends its result"),
+        },
+    }
+}
+
+/// The message given to `panic!`, which is text unless the panic was raised with another payload
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::from("no message"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::etc::test_util::temp_dir;
+    use crate::etc::{PartRun, Visualizer};
+    use crate::Solution;
+    use std::{fmt::Write, fs};
+
+    fn is_active(token: &CancelToken) -> bool {
+        let active = ACTIVE.lock().unwrap();
+        active
+            .iter()
+            .any(|active| active.ptr_eq(&Arc::downgrade(&token.cancelled)))
+    }
+
+    #[test]
+    fn cancel_on_ctrlc() {
+        let token = CancelToken::new();
+        assert!(token.check().is_ok());
+
+        token.with_ctrlc(|| assert!(is_active(&token)));
+        assert!(!is_active(&token));
+
+        token.clone().cancel();
+        assert!(matches!(token.check(), Err(AocError::Cancelled)));
+    }
+
+    /// Counts until cancelled
+    fn count(_input: &str, _part: Option<Part>, ctx: &Context) -> Result<DayRun, AocError> {
+        let mut n: u64 = 0;
+        while !ctx.cancel.is_cancelled() {
+            n += 1;
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        Ok(DayRun {
+            parse: Duration::ZERO,
+            parse_alloc: None,
+            part1: Some(PartRun {
+                solution: Solution::from(n),
+                elapsed: Duration::ZERO,
+                alloc: None,
+            }),
+            part2: None,
+        })
+    }
+
+    fn instant(_input: &str, _part: Option<Part>, _ctx: &Context) -> Result<DayRun, AocError> {
+        Ok(DayRun {
+            parse: Duration::ZERO,
+            parse_alloc: None,
+            part1: None,
+            part2: None,
+        })
+    }
+
+    #[test]
+    fn timeout() {
+        let timeout = Duration::from_millis(20);
+        let ctx = Context::default();
+
+        assert!(matches!(
+            run_with_timeout(count, "", None, Some(timeout), &ctx),
+            Err(AocError::TimedOut(t)) if t == timeout
+        ));
+        assert!(ctx.cancel.is_cancelled());
+
+        let ctx = Context::default();
+        assert!(run_with_timeout(instant, "", None, Some(Duration::from_secs(5)), &ctx).is_ok());
+        assert!(run_with_timeout(instant, "", None, None, &ctx).is_ok());
+    }
+
+    #[test]
+    fn visualization_with_timeout() {
+        fn drawing(input: &str, part: Option<Part>, ctx: &Context) -> Result<DayRun, AocError> {
+            for n in 1..=3 {
+                ctx.visualizer
+                    .frame(Duration::ZERO, |f| writeln!(f, "frame {n}"));
+            }
+            instant(input, part, ctx)
+        }
+
+        let dir = temp_dir("timeout-visualization");
+        let path = dir.join("visualization.txt");
+        let ctx = Context {
+            visualizer: Visualizer::file(&path).unwrap(),
+            ..Context::default()
+        };
+        assert!(run_with_timeout(drawing, "", None, Some(Duration::from_secs(5)), &ctx).is_ok());
+
+        // The solver thread holds no visualizer anymore, the file is flushed with the last one
+        drop(ctx);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap().matches("frame").count(),
+            3
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn panics() {
+        fn panicking(input: &str, _part: Option<Part>, _ctx: &Context) -> Result<DayRun, AocError> {
+            panic!("`{input}` is not a map");
+        }
+
+        let ctx = Context::default();
+        for timeout in [None, Some(Duration::from_secs(5))] {
+            assert!(matches!(
+                run_with_timeout(panicking, "x", None, timeout, &ctx),
+                Err(AocError::Panicked(message)) if message == "`x` is not a map"
+            ));
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use crate::etc::cancel::CancelToken;
+use crate::etc::{Artifacts, Visualizer};
+
+/// What the runner hands to the parts of a day besides their input: the token telling them to
+/// stop, the visualizer they draw with and where they write their files. A solver that spawns
+/// threads gives them a clone. The default context is never cancelled, draws nothing and
+/// cannot write, which is what the tests need
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    pub cancel: CancelToken,
+    pub visualizer: Visualizer,
+    pub artifacts: Artifacts,
+}
@@ -0,0 +1,221 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info_span};
+
+use crate::etc::alloc::{self, AllocStats};
+use crate::{AocError, Context, Solution, SolutionPair};
+
+/// A puzzle split in stages, so that the input is parsed once and each part can be run and
+/// timed on its own
+pub trait Day {
+    /// The input once parsed, shared by both parts
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError>;
+
+    fn part1(parsed: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError>;
+
+    fn part2(parsed: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError>;
+
+    /// Parses the input and solves both parts with the default context, mostly useful in the
+    /// tests
+    #[allow(dead_code)]
+    fn solve(input: &str) -> Result<SolutionPair, AocError> {
+        let parsed = Self::parse(input)?;
+        let ctx = Context::default();
+
+        Ok((Self::part1(&parsed, &ctx)?, Self::part2(&parsed, &ctx)?))
+    }
+
+    /// Parses the input and solves the requested parts, or both if none is given, timing every
+    /// stage separately and counting its allocations. Stops at the first stage that fails. Each
+    /// stage runs in its own span
+    fn run(input: &str, part: Option<Part>, ctx: &Context) -> Result<DayRun, AocError> {
+        let ((parsed, parse_alloc), parse) = info_span!("parse").in_scope(|| {
+            let time = Instant::now();
+            let parsed = alloc::measure(|| Self::parse(input));
+            let elapsed = time.elapsed();
+            debug!(?elapsed, ok = parsed.0.is_ok(), "parsed");
+            (parsed, elapsed)
+        });
+        let parsed = parsed?;
+
+        let run_part = |selected: Part,
+                        solver: fn(&Self::Parsed, &Context) -> Result<Solution, AocError>|
+         -> Result<Option<PartRun>, AocError> {
+            if part.is_some_and(|p| p != selected) {
+                return Ok(None);
+            }
+
+            let _span = info_span!("part", part = selected.number()).entered();
+            let time = Instant::now();
+            let (solution, alloc) = alloc::measure(|| solver(&parsed, ctx));
+            let elapsed = time.elapsed();
+            match &solution {
+                Ok(solution) => debug!(?elapsed, %solution, "solved"),
+                Err(e) => debug!(?elapsed, error = %e, "failed"),
+            }
+
+            Ok(Some(PartRun {
+                solution: solution?,
+                elapsed,
+                alloc,
+            }))
+        };
+
+        Ok(DayRun {
+            parse,
+            parse_alloc,
+            part1: run_part(Part::One, Self::part1)?,
+            part2: run_part(Part::Two, Self::part2)?,
+        })
+    }
+
+    /// Parses the input into a value whose parts can be solved as many times as needed, without
+    /// knowing the type of the parsed input. Used to time each stage on its own in the benches
+    fn parse_boxed(input: &str) -> Result<Box<dyn ParsedInput>, AocError>
+    where
+        Self: Sized + 'static,
+    {
+        Ok(Box::new(Parsed::<Self>(Self::parse(input)?)))
+    }
+}
+
+/// Runs a day on the given input, see [`Day::run`]
+pub type DayRunner = fn(&str, Option<Part>, &Context) -> Result<DayRun, AocError>;
+
+/// Parses the input of a day, see [`Day::parse_boxed`]
+pub type DayParser = fn(&str) -> Result<Box<dyn ParsedInput>, AocError>;
+
+/// The parsed input of some day, ready to solve its parts
+pub trait ParsedInput {
+    fn part1(&self, ctx: &Context) -> Result<Solution, AocError>;
+
+    fn part2(&self, ctx: &Context) -> Result<Solution, AocError>;
+}
+
+struct Parsed<D: Day>(D::Parsed);
+
+impl<D: Day> ParsedInput for Parsed<D> {
+    fn part1(&self, ctx: &Context) -> Result<Solution, AocError> {
+        D::part1(&self.0, ctx)
+    }
+
+    fn part2(&self, ctx: &Context) -> Result<Solution, AocError> {
+        D::part2(&self.0, ctx)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+pub enum Part {
+    #[value(name = "1")]
+    #[serde(rename = "1")]
+    One,
+    #[value(name = "2")]
+    #[serde(rename = "2")]
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// The solutions and timings of a day, a part is `None` when it was not requested
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub parse: Duration,
+    /// What the parsing allocated, `None` without the `memory` feature
+    pub parse_alloc: Option<AllocStats>,
+    pub part1: Option<PartRun>,
+    pub part2: Option<PartRun>,
+}
+
+impl DayRun {
+    /// Time spent in all the stages
+    pub fn elapsed(&self) -> Duration {
+        self.parse
+            + self.part1.as_ref().map(|p| p.elapsed).unwrap_or_default()
+            + self.part2.as_ref().map(|p| p.elapsed).unwrap_or_default()
+    }
+
+    /// What all the stages allocated, `None` without the `memory` feature
+    pub fn alloc(&self) -> Option<AllocStats> {
+        let parts = [&self.part1, &self.part2].into_iter().flatten();
+        let stages = std::iter::once(self.parse_alloc).chain(parts.map(|p| p.alloc));
+
+        Some(AllocStats::total(stages.collect::<Option<Vec<_>>>()?))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartRun {
+    pub solution: Solution,
+    pub elapsed: Duration,
+    pub alloc: Option<AllocStats>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Sum;
+
+    impl Day for Sum {
+        type Parsed = Vec<u64>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+            input.split(',').map(|n| Ok(n.parse()?)).collect()
+        }
+
+        fn part1(parsed: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+            Ok(Solution::from(parsed.iter().sum::<u64>()))
+        }
+
+        fn part2(parsed: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+            Ok(Solution::from(parsed.iter().product::<u64>()))
+        }
+    }
+
+    #[test]
+    fn solve_both_parts() {
+        assert_eq!(
+            Sum::solve("2,3,4").unwrap(),
+            (Solution::from(9_u64), Solution::from(24_u64))
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            Sum::run("2,a", None, &Context::default()),
+            Err(AocError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn run_single_part() {
+        let ctx = Context::default();
+        let run = Sum::run("2,3,4", Some(Part::Two), &ctx).unwrap();
+        assert!(run.part1.is_none());
+        assert_eq!(run.part2.unwrap().solution, Solution::from(24_u64));
+
+        let run = Sum::run("2,3,4", None, &ctx).unwrap();
+        assert_eq!(run.alloc().is_some(), cfg!(feature = "memory"));
+        assert_eq!(run.part1.unwrap().solution, Solution::from(9_u64));
+    }
+
+    #[test]
+    fn parse_once() {
+        let parsed = Sum::parse_boxed("2,3,4").unwrap();
+        let ctx = Context::default();
+        assert_eq!(parsed.part2(&ctx).unwrap(), Solution::from(24_u64));
+        assert_eq!(parsed.part2(&ctx).unwrap(), Solution::from(24_u64));
+        assert!(Sum::parse_boxed("").is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io,
+    num::ParseIntError,
+    time::Duration,
+};
+
+/// Why a day could not be solved
+#[derive(Debug)]
+pub enum AocError {
+    /// The input does not have the expected format
+    InvalidInput(String),
+    /// The input is valid but the puzzle has no answer for it
+    NoSolution(String),
+    Io(io::Error),
+    /// A solver running as another program could not be built or run, or printed no answer
+    External(String),
+    /// The solver stopped early after being cancelled, see [`crate::etc::cancel`]
+    Cancelled,
+    /// The solver did not finish in time
+    TimedOut(Duration),
+    /// The solver panicked, with the message of the panic
+    Panicked(String),
+}
+
+impl AocError {
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        AocError::InvalidInput(message.into())
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        AocError::NoSolution(message.into())
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::InvalidInput(message) => write!(f, "invalid input: {message}"),
+            AocError::NoSolution(message) => write!(f, "no solution: {message}"),
+            AocError::Io(e) => write!(f, "{e}"),
+            AocError::External(message) => write!(f, "external solver: {message}"),
+            AocError::Cancelled => write!(f, "cancelled"),
+            AocError::TimedOut(timeout) => write!(f, "timed out after {timeout:?}"),
+            AocError::Panicked(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(e: ParseIntError) -> Self {
+        AocError::InvalidInput(format!("expected a number, {e}"))
+    }
+}
@@ -1,3 +1,19 @@
+pub mod alloc;
+pub mod artifacts;
+pub mod cancel;
+pub mod context;
+pub mod day;
+pub mod error;
+pub mod registry;
 pub mod solution;
+#[cfg(test)]
+pub(crate) mod test_util;
+pub mod visualize;
 
-pub use solution::Solution;
\ No newline at end of file
+pub use artifacts::Artifacts;
+pub use context::Context;
+pub use day::{Day, DayParser, DayRun, DayRunner, ParsedInput, Part, PartRun};
+pub use error::AocError;
+pub(crate) use registry::register_day;
+pub use solution::Solution;
+pub use visualize::Visualizer;
@@ -0,0 +1,154 @@
+use linkme::distributed_slice;
+
+use crate::etc::{DayParser, DayRunner, Part};
+
+/// Every registered day, filled by [`register_day!`] in the source of each day
+#[distributed_slice]
+pub static DAYS: [DayInfo];
+
+/// Which parts of a day have a solution, the others are still the empty template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    /// Only the first part is solved
+    PartOne,
+    Stub,
+}
+
+/// A registered day and how to run it
+#[derive(Debug)]
+pub struct DayInfo {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub status: Status,
+    pub run: DayRunner,
+    /// Parses the input alone, so that the stages can be timed one by one
+    pub parse: DayParser,
+}
+
+impl DayInfo {
+    /// Tells if at least the first part has a solution
+    pub fn is_solved(&self) -> bool {
+        self.status != Status::Stub
+    }
+
+    pub fn is_part_solved(&self, part: Part) -> bool {
+        match self.status {
+            Status::Solved => true,
+            Status::PartOne => part == Part::One,
+            Status::Stub => false,
+        }
+    }
+
+    /// The parts to run when `part` is requested: a day with only its first part solved runs
+    /// that part alone when both are requested
+    pub fn parts_to_run(&self, part: Option<Part>) -> Option<Part> {
+        match (self.status, part) {
+            (Status::PartOne, None) => Some(Part::One),
+            _ => part,
+        }
+    }
+}
+
+/// The registered days of a year, in order
+pub fn days(year: u16) -> Vec<&'static DayInfo> {
+    let mut days: Vec<_> = DAYS.iter().filter(|info| info.year == year).collect();
+    days.sort_by_key(|info| info.day);
+    days
+}
+
+pub fn get(year: u16, day: u8) -> Option<&'static DayInfo> {
+    DAYS.iter()
+        .find(|info| info.year == year && info.day == day)
+}
+
+/// Tells if a day is registered and has a solution, for one part at least
+pub fn is_solved(year: u16, day: u8) -> bool {
+    get(year, day).is_some_and(DayInfo::is_solved)
+}
+
+/// Tells if a day is registered and the given part has a solution
+pub fn is_part_solved(year: u16, day: u8, part: Part) -> bool {
+    get(year, day).is_some_and(|info| info.is_part_solved(part))
+}
+
+/// Registers a type implementing [`Day`](crate::Day), a solved day unless `PartOne` or `Stub`
+/// is given:
+/// ```ignore
+/// register_day!(Day01, 2024, 1, "Historian Hysteria");
+/// register_day!(Day16, 2024, 16, "Reindeer Maze", PartOne);
+/// register_day!(Day17, 2024, 17, "Chronospatial Computer", Stub);
+/// ```
+macro_rules! register_day {
+    (@status) => {
+        $crate::etc::registry::Status::Solved
+    };
+    (@status $status:ident) => {
+        $crate::etc::registry::Status::$status
+    };
+    ($solver:ty, $year:literal, $day:literal, $title:literal $(, $status:ident)?) => {
+        // In its own scope so that a module can register several days
+        const _: () = {
+            #[linkme::distributed_slice($crate::etc::registry::DAYS)]
+            static REGISTRATION: $crate::etc::registry::DayInfo = $crate::etc::registry::DayInfo {
+                year: $year,
+                day: $day,
+                title: $title,
+                status: $crate::etc::registry::register_day!(@status $($status)?),
+                run: <$solver as $crate::etc::Day>::run,
+                parse: <$solver as $crate::etc::Day>::parse_boxed,
+            };
+        };
+    };
+}
+pub(crate) use register_day;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn registered_once() {
+        let mut seen = HashSet::new();
+        for info in DAYS.iter() {
+            assert!(
+                seen.insert((info.year, info.day)),
+                "{} day {} is registered twice",
+                info.year,
+                info.day
+            );
+        }
+    }
+
+    #[test]
+    fn days_of_2024() {
+        let days = days(2024);
+        assert_eq!(
+            days.iter().map(|info| info.day).collect::<Vec<_>>(),
+            (1..=25).collect::<Vec<_>>()
+        );
+        assert_eq!(days[0].title, "Historian Hysteria");
+
+        assert!(is_solved(2024, 16));
+        assert!(is_part_solved(2024, 16, Part::One));
+        assert!(!is_part_solved(2024, 16, Part::Two));
+        assert!(is_part_solved(2024, 15, Part::Two));
+        assert!(!is_solved(2024, 25));
+        assert!(!is_part_solved(2024, 25, Part::One));
+        assert!(!is_solved(2023, 5));
+        assert!(get(2024, 26).is_none());
+    }
+
+    #[test]
+    fn parts_to_run() {
+        let (solved, part_one, stub) = (get(2024, 15), get(2024, 16), get(2024, 25));
+        let (solved, part_one, stub) = (solved.unwrap(), part_one.unwrap(), stub.unwrap());
+
+        assert_eq!(solved.parts_to_run(None), None);
+        assert_eq!(part_one.parts_to_run(None), Some(Part::One));
+        assert_eq!(part_one.parts_to_run(Some(Part::Two)), Some(Part::Two));
+        assert_eq!(stub.parts_to_run(None), None);
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Serialize, Serializer};
 use std::fmt::{Display, Formatter, Result};
 use Solution::*;
 
@@ -67,3 +68,23 @@ impl From<&str> for Solution {
         Self::Str(sol.to_owned())
     }
 }
+
+impl Serialize for Solution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            I8(x) => x.serialize(serializer),
+            I16(x) => x.serialize(serializer),
+            I32(x) => x.serialize(serializer),
+            I64(x) => x.serialize(serializer),
+            I128(x) => x.serialize(serializer),
+            Isize(x) => x.serialize(serializer),
+            U8(x) => x.serialize(serializer),
+            U16(x) => x.serialize(serializer),
+            U32(x) => x.serialize(serializer),
+            U64(x) => x.serialize(serializer),
+            U128(x) => x.serialize(serializer),
+            Usize(x) => x.serialize(serializer),
+            Str(x) => x.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Helpers shared by the tests of the library and of the runner
+
+use std::{fs, path::PathBuf};
+
+/// An empty directory for a test in the temporary directory, emptied if an earlier run left
+/// it behind. The test removes it once done
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,144 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+    thread,
+    time::Duration,
+};
+
+use crate::etc::{AocError, Artifacts};
+
+/// Where the solvers draw their visualization, chosen at runtime with `--visualize`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Mode {
+    /// Nothing is drawn
+    #[default]
+    None,
+    /// Each frame replaces the previous one in the terminal, slowed down to be followed
+    Terminal,
+    /// The frames are written one after the other to `visualization.txt` in the artifacts of
+    /// the day, as fast as the solver goes. `cat` replays them
+    File,
+}
+
+/// Draws the frames of a solver. A disabled one, the default, costs a single check per frame
+/// and never builds the frame
+#[derive(Debug, Clone, Default)]
+pub struct Visualizer {
+    output: Option<Arc<Output>>,
+}
+
+#[derive(Debug)]
+enum Output {
+    Terminal,
+    /// Flushed when the last handle is dropped
+    File(Mutex<BufWriter<File>>),
+}
+
+impl Visualizer {
+    /// Clears the screen before each frame, so that a frame starts with it in a file too
+    const CLEAR: &'static str = "\x1b[2J\x1b[H";
+
+    pub fn terminal() -> Self {
+        Visualizer {
+            output: Some(Arc::new(Output::Terminal)),
+        }
+    }
+
+    /// Writes the frames to a file, replacing it
+    pub fn file(path: &Path) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = BufWriter::new(File::create(path)?);
+
+        Ok(Visualizer {
+            output: Some(Arc::new(Output::File(Mutex::new(file)))),
+        })
+    }
+
+    /// The visualizer of a day in the given mode
+    pub fn for_day(mode: Mode, artifacts: &Artifacts) -> Result<Self, AocError> {
+        match mode {
+            Mode::None => Ok(Visualizer::default()),
+            Mode::Terminal => Ok(Visualizer::terminal()),
+            Mode::File => Ok(Visualizer::file(&artifacts.path("visualization.txt")?)?),
+        }
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.output.is_some()
+    }
+
+    /// Draws a frame replacing the previous one, then waits for `delay` in the terminal so that
+    /// it can be seen. `draw` is only called when the visualizer is enabled
+    #[inline]
+    pub fn frame(&self, delay: Duration, draw: impl FnOnce(&mut String) -> fmt::Result) {
+        if let Some(output) = &self.output {
+            let mut frame = String::from(Self::CLEAR);
+            // Drawing into a string cannot fail
+            let _ = draw(&mut frame);
+            output.write(&frame, delay);
+        }
+    }
+}
+
+impl Output {
+    /// A visualization that cannot be written does not stop the solver
+    fn write(&self, frame: &str, delay: Duration) {
+        match self {
+            Output::Terminal => {
+                let mut stdout = io::stdout().lock();
+                let _ = stdout
+                    .write_all(frame.as_bytes())
+                    .and_then(|_| stdout.flush());
+                drop(stdout);
+                thread::sleep(delay);
+            }
+            Output::File(file) => {
+                let mut file = file.lock().unwrap_or_else(PoisonError::into_inner);
+                let _ = file.write_all(frame.as_bytes());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::etc::test_util::temp_dir;
+    use std::fmt::Write;
+
+    #[test]
+    fn disabled_by_default() {
+        let visualizer = Visualizer::default();
+        assert!(!visualizer.is_enabled());
+        visualizer.frame(Duration::from_secs(10), |_| {
+            unreachable!("nothing is drawn")
+        });
+    }
+
+    #[test]
+    fn frames_in_file() {
+        let dir = temp_dir("visualize");
+        let path = dir.join("frames/1.txt");
+        let visualizer = Visualizer::file(&path).unwrap();
+        assert!(visualizer.is_enabled());
+
+        // The clones share the file, which is flushed once the last one is dropped
+        let clone = visualizer.clone();
+        for n in 1..=2 {
+            clone.frame(Duration::from_secs(10), |f| writeln!(f, "frame {n}"));
+        }
+        drop(clone);
+        drop(visualizer);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "\x1b[2J\x1b[Hframe 1\n\x1b[2J\x1b[Hframe 2\n"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    env, fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, PoisonError,
+    },
+    thread,
+    time::Duration,
+};
+
+use tracing::{debug, trace};
+
+use crate::etc::cancel::CancelToken;
+use crate::{register_day, AocError, Context, Day, Solution};
+
+/// A day of 2023, solved in C by `YYYY/dayNN/part1.c` and `part2.c` next to this crate. Each
+/// program reads `input.txt` from its working directory and prints its answer last, as
+/// `Final answer: N`
+pub struct CDay<const DAY: u8>;
+
+register_day!(CDay<1>, 2023, 1, "Trebuchet?!");
+register_day!(CDay<2>, 2023, 2, "Cube Conundrum");
+register_day!(CDay<3>, 2023, 3, "Gear Ratios");
+register_day!(CDay<4>, 2023, 4, "Scratchcards");
+
+/// The programs of a day, built, and the directory holding the input they read
+pub struct Workdir {
+    dir: PathBuf,
+    programs: [PathBuf; 2],
+}
+
+impl Drop for Workdir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+impl<const DAY: u8> Day for CDay<DAY> {
+    type Parsed = Workdir;
+
+    /// Builds the programs if their sources changed and writes the input where they read it,
+    /// so the first run also times the compiler
+    fn parse(input: &str) -> Result<Self::Parsed, AocError> {
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+        let programs = [build(DAY, 1)?, build(DAY, 2)?];
+
+        let dir = env::temp_dir().join(format!(
+            "aoc-2023-day{:02}-{}-{}",
+            DAY,
+            process::id(),
+            RUNS.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir)?;
+        let workdir = Workdir { dir, programs };
+
+        // The programs stop reading a line at its newline, the last one included
+        let mut input = input.to_owned();
+        if !input.ends_with('\n') {
+            input.push('\n');
+        }
+        fs::write(workdir.dir.join("input.txt"), input)?;
+
+        Ok(workdir)
+    }
+
+    fn part1(parsed: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        run(&parsed.programs[0], &parsed.dir, &ctx.cancel)
+    }
+
+    fn part2(parsed: &Self::Parsed, ctx: &Context) -> Result<Solution, AocError> {
+        run(&parsed.programs[1], &parsed.dir, &ctx.cancel)
+    }
+}
+
+/// Where the C solutions are, one `dayNN` directory per day
+pub fn source_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../2023")
+}
+
+/// The puzzle input that comes with the C solution of a day, if there is one
+pub fn bundled_input(year: u16, day: u8) -> Option<PathBuf> {
+    let path = source_dir()
+        .join(format!("day{:02}", day))
+        .join("input.txt");
+
+    (year == 2023 && path.is_file()).then_some(path)
+}
+
+/// Compiles a part with `$CC`, or `cc`, unless it is already built from the current source
+fn build(day: u8, part: u8) -> Result<PathBuf, AocError> {
+    /// The days running in parallel share their programs
+    static BUILD: Mutex<()> = Mutex::new(());
+
+    let source = source_dir()
+        .join(format!("day{:02}", day))
+        .join(format!("part{part}.c"));
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target/c/2023")
+        .join(format!("day{:02}", day))
+        .join(format!("part{part}"));
+
+    let _build = BUILD.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified());
+    let source_modified = modified(&source)
+        .map_err(|e| AocError::External(format!("{} cannot be read: {e}", source.display())))?;
+    if modified(&program).is_ok_and(|built| built >= source_modified) {
+        return Ok(program);
+    }
+
+    if let Some(dir) = program.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let compiler = env::var_os("CC").unwrap_or_else(|| "cc".into());
+    debug!(source = %source.display(), compiler = ?compiler, "compiling");
+    let output = Command::new(&compiler)
+        .args(["-O2", "-o"])
+        .arg(&program)
+        .arg(&source)
+        .output()
+        .map_err(|e| {
+            AocError::External(format!("could not run {}: {e}", compiler.to_string_lossy()))
+        })?;
+    if !output.status.success() {
+        return Err(AocError::External(format!(
+            "could not compile {}: {}",
+            source.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(program)
+}
+
+/// Runs a program in `dir` and reads its answer. The program is killed if the day is cancelled
+fn run(program: &Path, dir: &Path, cancel: &CancelToken) -> Result<Solution, AocError> {
+    let mut child = Command::new(program)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| AocError::External(format!("could not run {}: {e}", program.display())))?;
+
+    // Read while it runs so that it never blocks on a full pipe
+    let mut stdout = child.stdout.take().expect("the output is piped");
+    let reader = thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).map(|_| output)
+    });
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if cancel.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(AocError::Cancelled);
+        }
+        thread::sleep(Duration::from_micros(100));
+    };
+
+    let output = reader.join().expect("the reader does not panic")?;
+    trace!(program = %program.display(), lines = output.lines().count(), "program finished");
+    if !status.success() {
+        return Err(AocError::External(format!(
+            "{} failed, {status}",
+            program.display()
+        )));
+    }
+
+    parse_answer(&output)
+}
+
+/// The answer in the last `Final answer: N` line, whatever its case
+fn parse_answer(output: &str) -> Result<Solution, AocError> {
+    const PREFIX: &str = "final answer:";
+
+    let answer = output.lines().rev().find_map(|line| {
+        // ASCII lowercase keeps the byte offsets of the line
+        let idx = line.to_ascii_lowercase().find(PREFIX)?;
+        Some(line[idx + PREFIX.len()..].trim())
+    });
+
+    match answer {
+        Some(answer) => Ok(answer
+            .parse::<i64>()
+            .map(Solution::from)
+            .unwrap_or_else(|_| Solution::from(answer))),
+        None => Err(AocError::External(format!(
+            "no final answer in the output {:?}",
+            output.lines().last().unwrap_or_default()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::etc::registry;
+
+    #[test]
+    fn answers() {
+        assert_eq!(
+            parse_answer("12\n34\nFinal answer: 46\n").unwrap(),
+            Solution::from(46_i64)
+        );
+        assert_eq!(
+            parse_answer("Final Answer: -3").unwrap(),
+            Solution::from(-3_i64)
+        );
+        assert_eq!(
+            parse_answer("Final answer: abc\n").unwrap(),
+            Solution::from("abc")
+        );
+        assert!(matches!(
+            parse_answer("File couldn't open"),
+            Err(AocError::External(_))
+        ));
+    }
+
+    #[test]
+    fn registered() {
+        let days = registry::days(2023);
+        assert_eq!(
+            days.iter().map(|info| info.day).collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+        assert!(days.iter().all(|info| info.is_solved()));
+        assert!(bundled_input(2023, 1).is_some());
+        assert!(bundled_input(2024, 1).is_none());
+    }
+
+    #[test]
+    fn example() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        let (p1, p2) = CDay::<2>::solve(input).unwrap();
+        assert_eq!(p1, Solution::from(8_i64));
+        assert_eq!(p2, Solution::from(2286_i64));
+    }
+}
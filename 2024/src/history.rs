@@ -0,0 +1,295 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use advent_of_code::etc::Part;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+
+use crate::bench::DayBench;
+
+/// A benchmark of a day saved in the history, one per line of the JSONL file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Short hash of the commit that was benchmarked, `unknown` outside of a git repository
+    pub commit: String,
+    /// Whether the working tree had uncommitted changes
+    pub dirty: bool,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub part: Option<Part>,
+    #[serde(flatten)]
+    pub bench: DayBench,
+}
+
+/// Change of the median total time of a day compared to its baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub year: u16,
+    pub day: u8,
+    pub baseline_commit: String,
+    pub baseline_ms: f64,
+    pub current_ms: f64,
+    /// Relative change in percent, positive when slower
+    pub change: f64,
+}
+
+impl HistoryEntry {
+    pub fn new(revision: &Revision, part: Option<Part>, bench: DayBench) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        HistoryEntry {
+            commit: revision.commit.clone(),
+            dirty: revision.dirty,
+            timestamp,
+            part,
+            bench,
+        }
+    }
+}
+
+/// The commit checked out in the crate directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    pub commit: String,
+    pub dirty: bool,
+}
+
+impl Revision {
+    pub fn current() -> Self {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        };
+
+        match git(&["rev-parse", "--short", "HEAD"]) {
+            Some(commit) => Revision {
+                commit,
+                dirty: git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()),
+            },
+            None => Revision {
+                commit: String::from("unknown"),
+                dirty: false,
+            },
+        }
+    }
+}
+
+/// Default location of the history file
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("target/aoc-bench/history.jsonl")
+}
+
+/// Loads every entry of the history, a missing file is an empty history
+pub fn load(path: &Path) -> Result<Vec<HistoryEntry>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("Could not read {}: {e}", path.display())),
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{}:{} is not a valid entry: {e}", path.display(), idx + 1))
+        })
+        .collect()
+}
+
+/// Appends the entries at the end of the history, creating it if needed
+pub fn append(path: &Path, entries: &[HistoryEntry]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Could not open {}: {e}", path.display()))?;
+
+    for entry in entries {
+        let line = serde_json::to_string(entry)
+            .map_err(|e| format!("Could not serialize the benchmark: {e}"))?;
+        writeln!(file, "{line}").map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Finds the most recent entry of a day benchmarked with the same parts, only in the commits
+/// starting with `commit` if given
+pub fn baseline<'a>(
+    history: &'a [HistoryEntry],
+    year: u16,
+    day: u8,
+    part: Option<Part>,
+    commit: Option<&str>,
+) -> Option<&'a HistoryEntry> {
+    history.iter().rev().find(|entry| {
+        entry.bench.year == year
+            && entry.bench.day == day
+            && entry.part == part
+            && commit.is_none_or(|commit| entry.commit.starts_with(commit))
+    })
+}
+
+impl Comparison {
+    pub fn new(baseline: &HistoryEntry, current: &DayBench) -> Self {
+        let baseline_ms = baseline.bench.total.median;
+        let current_ms = current.total.median;
+
+        Comparison {
+            year: current.year,
+            day: current.day,
+            baseline_commit: baseline.commit.clone(),
+            baseline_ms,
+            current_ms,
+            change: match baseline_ms > 0. {
+                true => (current_ms - baseline_ms) / baseline_ms * 100.,
+                false => 0.,
+            },
+        }
+    }
+
+    /// Whether the day got slower than the threshold, in percent
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change > threshold
+    }
+}
+
+/// Prints the comparisons and returns how many days are regressions
+pub fn print_comparisons(comparisons: &[Comparison], threshold: f64) -> usize {
+    println!(
+        "\n{:<9}{:>12}{:>14}{:>14}{:>10}",
+        "Day".bold(),
+        "Baseline".bold(),
+        "Before".bold(),
+        "After".bold(),
+        "Change".bold()
+    );
+
+    let mut regressions = 0;
+    for comparison in comparisons {
+        let change = format!("{:>10}", format!("{:+.1}%", comparison.change));
+        let change = if comparison.is_regression(threshold) {
+            regressions += 1;
+            change.red().to_string()
+        } else if comparison.change < -threshold {
+            change.green().to_string()
+        } else {
+            change.dimmed().to_string()
+        };
+
+        println!(
+            "{:<9}{:>12}{:>14}{:>14}{}",
+            format!("{} {:02}", comparison.year, comparison.day),
+            comparison.baseline_commit,
+            format!("{:.4} ms", comparison.baseline_ms),
+            format!("{:.4} ms", comparison.current_ms),
+            change
+        );
+    }
+
+    regressions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bench::Stats;
+    use crate::test_util::temp_dir;
+
+    fn entry(commit: &str, day: u8, part: Option<Part>, median: f64) -> HistoryEntry {
+        let stats = Stats {
+            min: median,
+            median,
+            mean: median,
+            stddev: 0.,
+        };
+
+        HistoryEntry {
+            commit: String::from(commit),
+            dirty: false,
+            timestamp: 0,
+            part,
+            bench: DayBench {
+                year: 2024,
+                day,
+                runs: 1,
+                parse: stats,
+                part1: None,
+                part2: Some(stats),
+                total: stats,
+                timed_out: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn find_baseline() {
+        let history = [
+            entry("aaaaaaa", 1, None, 1.),
+            entry("bbbbbbb", 1, None, 2.),
+            entry("bbbbbbb", 1, Some(Part::Two), 3.),
+            entry("ccccccc", 2, None, 4.),
+        ];
+
+        assert_eq!(baseline(&history, 2024, 1, None, None), Some(&history[1]));
+        assert_eq!(
+            baseline(&history, 2024, 1, None, Some("aaa")),
+            Some(&history[0])
+        );
+        assert_eq!(
+            baseline(&history, 2024, 1, Some(Part::Two), None),
+            Some(&history[2])
+        );
+        assert_eq!(baseline(&history, 2024, 3, None, None), None);
+        assert_eq!(baseline(&history, 2023, 1, None, None), None);
+    }
+
+    #[test]
+    fn regression() {
+        let base = entry("aaaaaaa", 1, None, 10.);
+        let comparison = Comparison::new(&base, &entry("bbbbbbb", 1, None, 12.).bench);
+
+        assert!((comparison.change - 20.).abs() < 1e-9);
+        assert!(comparison.is_regression(10.));
+        assert!(!comparison.is_regression(25.));
+    }
+
+    #[test]
+    fn jsonl_round_trip() {
+        let dir = temp_dir("history");
+        let path = dir.join("history.jsonl");
+
+        let entries = [
+            entry("aaaaaaa", 1, None, 1.),
+            entry("bbbbbbb", 2, Some(Part::One), 2.),
+        ];
+        append(&path, &entries[..1]).unwrap();
+        append(&path, &entries[1..]).unwrap();
+
+        assert_eq!(load(&path).unwrap(), entries);
+        assert!(fs::read_to_string(&path).unwrap().starts_with(
+            r#"{"commit":"aaaaaaa","dirty":false,"timestamp":0,"part":null,"year":2024,"day":1,"#
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{external, DEFAULT_YEAR};
+
+/// Where the input of the days comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `YYYY/dayNN.txt` in the input directory, or the input that comes with an external
+    /// solution when there is none
+    Puzzle,
+    /// The same file for every day, mostly useful with a single day
+    File(PathBuf),
+    /// The text read from the standard input when the run started
+    Stdin(String),
+    /// `N.txt` in the `YYYY/dayNN` directory of the examples
+    Example(u8),
+}
+
+/// Finds and reads the input of each day
+#[derive(Debug, Clone)]
+pub struct Inputs {
+    pub dir: PathBuf,
+    pub examples_dir: PathBuf,
+    pub source: Source,
+}
+
+impl Inputs {
+    pub fn new(dir: &Path, examples_dir: &Path, source: Source) -> Self {
+        Inputs {
+            dir: dir.to_owned(),
+            examples_dir: examples_dir.to_owned(),
+            source,
+        }
+    }
+
+    /// Whether every day reads the same input, which rarely makes sense for more than one day
+    pub fn is_shared(&self) -> bool {
+        matches!(self.source, Source::File(_) | Source::Stdin(_))
+    }
+
+    /// The file read for a day, `None` for the standard input
+    pub fn path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        match &self.source {
+            Source::Puzzle => Some(puzzle_input(&self.dir, year, day)),
+            Source::File(path) => Some(path.clone()),
+            Source::Stdin(_) => None,
+            Source::Example(n) => Some(example_path(&self.examples_dir, year, day, *n)),
+        }
+    }
+
+    /// Reads the input of a day, which must not be empty
+    pub fn read(&self, year: u16, day: u8) -> Result<String, String> {
+        let input = match (&self.source, self.path(year, day)) {
+            (Source::Stdin(input), _) => input.clone(),
+            (_, Some(path)) => fs::read_to_string(&path).map_err(|e| {
+                format!(
+                    "The input of day {:02} of {year} at {} is missing or cannot be read: {e}",
+                    day,
+                    path.display()
+                )
+            })?,
+            (_, None) => unreachable!("only the standard input has no path"),
+        };
+
+        if input.trim().is_empty() {
+            return Err(format!("The input of day {:02} of {year} is empty", day));
+        }
+
+        Ok(input)
+    }
+}
+
+/// The default directory of the puzzle inputs, one directory per year inside the crate so that
+/// the runner works from any directory
+pub fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input")
+}
+
+/// The default directory of the examples, one `YYYY/dayNN` directory per day
+pub fn default_examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples")
+}
+
+/// The puzzle input of a day in `YYYY/dayNN.txt`. The inputs of the crate's year used to be
+/// right in the input directory, such a `dayNN.txt` is still used while it is not moved
+pub fn puzzle_path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    let file = format!("day{:02}.txt", day);
+    let path = dir.join(year.to_string()).join(&file);
+    let flat_path = dir.join(&file);
+    if year == DEFAULT_YEAR && !path.exists() && flat_path.exists() {
+        return flat_path;
+    }
+
+    path
+}
+
+/// The puzzle input read for a day, the one that comes with an external solution when it is
+/// not in the input directory
+pub fn puzzle_input(dir: &Path, year: u16, day: u8) -> PathBuf {
+    let path = puzzle_path(dir, year, day);
+    match external::bundled_input(year, day) {
+        Some(bundled) if !path.exists() => bundled,
+        _ => path,
+    }
+}
+
+pub fn example_path(examples_dir: &Path, year: u16, day: u8, n: u8) -> PathBuf {
+    examples_dir
+        .join(year.to_string())
+        .join(format!("day{:02}", day))
+        .join(format!("{n}.txt"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::etc::test_util::temp_dir;
+
+    #[test]
+    fn paths() {
+        let inputs = Inputs {
+            dir: PathBuf::from("in"),
+            examples_dir: PathBuf::from("ex"),
+            source: Source::Puzzle,
+        };
+        assert_eq!(
+            inputs.path(2024, 7),
+            Some(PathBuf::from("in/2024/day07.txt"))
+        );
+        assert_eq!(
+            inputs.path(2023, 7),
+            Some(PathBuf::from("in/2023/day07.txt"))
+        );
+
+        let inputs = Inputs {
+            source: Source::Example(2),
+            ..inputs
+        };
+        assert_eq!(
+            inputs.path(2024, 12),
+            Some(PathBuf::from("ex/2024/day12/2.txt"))
+        );
+
+        let inputs = Inputs {
+            source: Source::Stdin(String::from("1 2")),
+            ..inputs
+        };
+        assert_eq!(inputs.path(2024, 12), None);
+        assert_eq!(inputs.read(2024, 12), Ok(String::from("1 2")));
+    }
+
+    #[test]
+    fn empty_input() {
+        let dir = temp_dir("empty-input");
+        fs::create_dir_all(dir.join("2024")).unwrap();
+        fs::write(puzzle_path(&dir, 2024, 1), "\n").unwrap();
+
+        let inputs = Inputs {
+            dir: dir.clone(),
+            examples_dir: dir.clone(),
+            source: Source::Puzzle,
+        };
+        assert_eq!(
+            inputs.read(2024, 1),
+            Err(String::from("The input of day 01 of 2024 is empty"))
+        );
+        assert!(inputs.read(2024, 2).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn flat_layout() {
+        let dir = temp_dir("flat-input");
+        fs::write(dir.join("day03.txt"), "mul(2,4)").unwrap();
+        assert_eq!(puzzle_path(&dir, 2024, 3), dir.join("day03.txt"));
+        assert_eq!(puzzle_path(&dir, 2023, 3), dir.join("2023/day03.txt"));
+        assert_eq!(puzzle_path(&dir, 2024, 4), dir.join("2024/day04.txt"));
+
+        // Once moved to its year, the input is read from there
+        fs::create_dir_all(dir.join("2024")).unwrap();
+        fs::write(dir.join("2024/day03.txt"), "mul(3,5)").unwrap();
+        assert_eq!(puzzle_path(&dir, 2024, 3), dir.join("2024/day03.txt"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
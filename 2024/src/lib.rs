@@ -0,0 +1,14 @@
+//! The Advent of Code solutions and what is needed to run them: every day implements
+//! [`Day`] and is found through [`etc::registry`]
+pub mod days;
+pub mod etc;
+pub mod external;
+pub mod input;
+
+pub(crate) use etc::register_day;
+pub use etc::{AocError, Context, Day, DayRun, Part, Solution};
+
+pub type SolutionPair = (Solution, Solution);
+
+/// Year used when no other is given, the one whose days are in `src/days`
+pub const DEFAULT_YEAR: u16 = 2024;
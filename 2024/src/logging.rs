@@ -0,0 +1,59 @@
+use std::io::{self, IsTerminal};
+
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+
+use crate::cli::{LogArgs, LogFormat};
+
+/// What is logged when neither `--log-level` nor `RUST_LOG` is given
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::INFO;
+
+/// The filter of the logs: the level given on the command line, otherwise the directives of
+/// `RUST_LOG`, otherwise [`DEFAULT_LEVEL`]
+fn filter(level: Option<LevelFilter>, rust_log: Option<&str>) -> Result<EnvFilter, String> {
+    match (level, rust_log) {
+        (Some(level), _) => Ok(EnvFilter::default().add_directive(level.into())),
+        (None, Some(directives)) if !directives.trim().is_empty() => EnvFilter::try_new(directives)
+            .map_err(|e| format!("RUST_LOG is not a valid filter: {e}")),
+        (None, _) => Ok(EnvFilter::default().add_directive(DEFAULT_LEVEL.into())),
+    }
+}
+
+/// Writes the logs of the spans and events to the standard error, where they do not mix with
+/// the reports
+pub fn init(args: &LogArgs) -> Result<(), String> {
+    let filter = filter(args.log_level, std::env::var("RUST_LOG").ok().as_deref())?;
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr);
+
+    let result = match args.log_format {
+        LogFormat::Text => builder
+            .with_ansi(io::stderr().is_terminal())
+            .without_time()
+            .try_init(),
+        LogFormat::Json => builder.json().with_current_span(true).try_init(),
+    };
+
+    result.map_err(|e| format!("Could not set up the logs: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn filters() {
+        let filter = |level, rust_log| filter(level, rust_log).unwrap().to_string();
+
+        assert_eq!(filter(None, None), "info");
+        assert_eq!(filter(None, Some("")), "info");
+        assert_eq!(
+            filter(None, Some("advent_of_code::days=trace")),
+            "advent_of_code::days=trace"
+        );
+        assert_eq!(filter(Some(LevelFilter::DEBUG), Some("trace")), "debug");
+        assert_eq!(filter(Some(LevelFilter::OFF), None), "off");
+
+        assert!(super::filter(None, Some("=")).is_err());
+    }
+}
@@ -1,87 +1,46 @@
-mod days;
-mod etc;
-
-use days::{
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
-};
-use etc::solution::Solution;
-use std::fs::read_to_string;
-use std::time::Instant;
-use std::env;
-
-pub type SolutionPair = (Solution, Solution);
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        panic!("Please provide the day(s) to run as a command-line argument.");
-    }
-
-    let days: Vec<u8> = args[1..]
-        .iter()
-        .map(|x| {
-            x.parse()
-                .unwrap_or_else(|v| panic!("Not a valid day: {}", v))
-        })
-        .collect();
-
-    let mut runtime = 0.0;
-
-    for day in days {
-        let func = get_day_solver(day);
-
-        let time = Instant::now();
-
-        let input = read_to_string(format!("./input/day{:02}.txt", day))
-            .unwrap_or_else(|e| panic!("The input file is missing or cannot be read: {}", e));
-
-        if input.is_empty() {
-            panic!("The input file is empty");
+mod answers;
+mod bench;
+mod cli;
+mod client;
+mod commands;
+mod dashboard;
+mod history;
+mod logging;
+mod pool;
+mod report;
+mod scaffold;
+mod selector;
+mod submit;
+#[cfg(test)]
+#[path = "etc/test_util.rs"]
+mod test_util;
+mod watch;
+
+use advent_of_code::etc::cancel;
+use clap::Parser;
+use cli::{Cli, Command};
+use owo_colors::OwoColorize;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    // The dashboard takes the whole terminal, the logs would be drawn over it
+    if !matches!(cli.command, Command::Dashboard { .. }) {
+        if let Err(e) = logging::init(&cli.log) {
+            eprintln!("{} {e}", "warning:".yellow().bold());
         }
-
-        let (p1, p2) = func(&input);
-
-        let elapsed_ms = time.elapsed().as_nanos() as f64 / 1_000_000.0;
-
-        println!("\n=== Day {:02} ===", day);
-        println!("  · Part 1: {}", p1);
-        println!("  · Part 2: {}", p2);
-        println!("  · Elapsed: {:.4} ms", elapsed_ms);
-
-        runtime += elapsed_ms;
     }
 
-    println!("Total runtime: {:.4} ms", runtime);
-}
+    if let Err(e) = cancel::handle_ctrlc() {
+        eprintln!("{} {e}", "warning:".yellow().bold());
+    }
 
-fn get_day_solver(day: u8) -> fn(&str) -> SolutionPair {
-    match day {
-        1 => day01::solve,
-        2 => day02::solve,
-        3 => day03::solve,
-        4 => day04::solve,
-        5 => day05::solve,
-        6 => day06::solve,
-        7 => day07::solve,
-        8 => day08::solve,
-        9 => day09::solve,
-        10 => day10::solve,
-        11 => day11::solve,
-        12 => day12::solve,
-        13 => day13::solve,
-        14 => day14::solve,
-        15 => day15::solve,
-        16 => day16::solve,
-        17 => day17::solve,
-        18 => day18::solve,
-        19 => day19::solve,
-        20 => day20::solve,
-        21 => day21::solve,
-        22 => day22::solve,
-        23 => day23::solve,
-        24 => day24::solve,
-        25 => day25::solve,
-        _ => unimplemented!(),
+    match commands::execute(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{} {e}", "error:".red().bold());
+            ExitCode::FAILURE
+        }
     }
 }
@@ -0,0 +1,95 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Runs `work` on every item using up to `jobs` threads, and hands the results to `output` in
+/// the order of the items as soon as all the previous ones are done
+pub fn run_ordered<T, R>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut output: impl FnMut(R),
+) where
+    T: Sync,
+    R: Send,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+
+    if jobs == 1 {
+        items.iter().map(&work).for_each(output);
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                if sender.send((idx, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // Results that arrived before the ones preceding them
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+
+        for (idx, result) in receiver {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&expected) {
+                output(result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn keeps_order() {
+        let items: Vec<u64> = (0..20).collect();
+
+        for jobs in [1, 4, 50] {
+            let mut results = vec![];
+            run_ordered(
+                &items,
+                jobs,
+                |&n| {
+                    // The first items are the slowest to finish
+                    thread::sleep(Duration::from_millis(20 - n));
+                    n * 2
+                },
+                |n| results.push(n),
+            );
+
+            assert_eq!(results, (0..20).map(|n| n * 2).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn no_items() {
+        let mut called = false;
+        run_ordered(&[] as &[u8], 4, |&n| n, |_| called = true);
+
+        assert!(!called);
+    }
+}
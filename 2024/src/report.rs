@@ -0,0 +1,568 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use advent_of_code::etc::alloc::AllocStats;
+use advent_of_code::etc::{registry, DayRun, Part, PartRun, Solution};
+use clap::ValueEnum;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::answers::{Answers, Verdict};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// Human readable output, printed as each day completes
+    #[default]
+    Text,
+    /// A single JSON document with every day
+    Json,
+    /// One row per day
+    Csv,
+}
+
+/// The results of a run over multiple days
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub days: Vec<DayReport>,
+    /// Sum of the time spent solving every day
+    pub total_ms: f64,
+    /// Time elapsed from the start of the first day to the end of the last one, lower than
+    /// `total_ms` when the days are solved in parallel
+    pub wall_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    /// SHA-256 of the input file, to tell apart runs on different inputs. `None` when the input
+    /// could not be read
+    pub input_hash: Option<String>,
+    pub parse_ms: f64,
+    /// What the parsing allocated, only with the `memory` feature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_alloc: Option<AllocStats>,
+    pub part1: Option<PartReport>,
+    pub part2: Option<PartReport>,
+    pub total_ms: f64,
+    /// What every stage allocated, only with the `memory` feature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+    /// Why the day could not be solved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Whether the day was stopped because it exceeded the timeout
+    pub timed_out: bool,
+    /// The files the day produced in its output directory
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PartReport {
+    pub answer: Solution,
+    pub time_ms: f64,
+    /// What the part allocated, only with the `memory` feature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+    /// Comparison with the stored answer, only when verifying
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<Verdict>,
+}
+
+/// Flattened [`DayReport`] used for the CSV rows
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    year: u16,
+    day: u8,
+    input_hash: Option<&'a str>,
+    parse_ms: f64,
+    part1: Option<&'a Solution>,
+    part1_ms: Option<f64>,
+    part1_status: Option<&'static str>,
+    part2: Option<&'a Solution>,
+    part2_ms: Option<f64>,
+    part2_status: Option<&'static str>,
+    total_ms: f64,
+    timed_out: bool,
+    error: Option<&'a str>,
+    alloc_bytes: Option<u64>,
+    alloc_count: Option<u64>,
+    peak_bytes: Option<u64>,
+    artifacts: usize,
+}
+
+impl DayReport {
+    pub fn new(year: u16, day: u8, input: &str, day_run: &DayRun) -> Self {
+        let part_report = |part: &Option<PartRun>| {
+            part.as_ref().map(|p| PartReport {
+                answer: p.solution.clone(),
+                time_ms: as_ms(p.elapsed),
+                alloc: p.alloc,
+                verdict: None,
+            })
+        };
+
+        DayReport {
+            year,
+            day,
+            input_hash: Some(input_hash(input)),
+            parse_ms: as_ms(day_run.parse),
+            parse_alloc: day_run.parse_alloc,
+            part1: part_report(&day_run.part1),
+            part2: part_report(&day_run.part2),
+            total_ms: as_ms(day_run.elapsed()),
+            alloc: day_run.alloc(),
+            error: None,
+            timed_out: false,
+            artifacts: vec![],
+        }
+    }
+
+    /// A day that could not be solved, `input` is `None` if it could not be read either
+    pub fn failed(year: u16, day: u8, input: Option<&str>, error: impl Display) -> Self {
+        DayReport {
+            year,
+            day,
+            input_hash: input.map(input_hash),
+            parse_ms: 0.,
+            parse_alloc: None,
+            part1: None,
+            part2: None,
+            total_ms: 0.,
+            alloc: None,
+            error: Some(error.to_string()),
+            timed_out: false,
+            artifacts: vec![],
+        }
+    }
+
+    fn parts(&self) -> [(Part, &Option<PartReport>); 2] {
+        [(Part::One, &self.part1), (Part::Two, &self.part2)]
+    }
+
+    /// Compares the answers with the stored ones and returns how many are wrong. The parts that
+    /// are not solved only have placeholder answers, they are left without a verdict
+    pub fn verify(&mut self, answers: &Answers) -> usize {
+        let (year, day) = (self.year, self.day);
+        let mut wrong = 0;
+
+        for (part, part_report) in [(Part::One, &mut self.part1), (Part::Two, &mut self.part2)] {
+            if !registry::is_part_solved(year, day, part) {
+                continue;
+            }
+            if let Some(part_report) = part_report {
+                let verdict = answers.verify(year, day, part, &part_report.answer);
+                if matches!(verdict, Verdict::Wrong { .. }) {
+                    wrong += 1;
+                }
+                part_report.verdict = Some(verdict);
+            }
+        }
+
+        wrong
+    }
+
+    /// Stores the answers of the solved parts that were run
+    pub fn record(&self, answers: &mut Answers) {
+        for (part, part_report) in self.parts() {
+            if !registry::is_part_solved(self.year, self.day, part) {
+                continue;
+            }
+            if let Some(part_report) = part_report {
+                answers.set(self.year, self.day, part, &part_report.answer);
+            }
+        }
+    }
+
+    pub fn print_text(&self) {
+        println!("\n=== {} Day {:02} ===", self.year, self.day);
+        if let Some(error) = &self.error {
+            match self.timed_out {
+                true => println!("  · {}", format!("Stopped: {error}").yellow()),
+                false => println!("  · {}", format!("Failed: {error}").red()),
+            }
+            self.print_artifacts();
+            return;
+        }
+
+        println!(
+            "  · Parse: {}",
+            with_alloc(self.parse_ms, &self.parse_alloc)
+        );
+        for (part, part_report) in self.parts() {
+            if let Some(part_report) = part_report {
+                let part = part.number();
+                let verdict = match &part_report.verdict {
+                    Some(Verdict::Correct) => format!(" {}", "✓".green()),
+                    Some(Verdict::Wrong { expected }) => {
+                        format!(" {}", format!("✗ expected {expected}").red())
+                    }
+                    Some(Verdict::Unknown) => format!(" {}", "? no stored answer".yellow()),
+                    None => String::new(),
+                };
+                let answer = match &part_report.verdict {
+                    Some(Verdict::Wrong { .. }) => part_report.answer.red().to_string(),
+                    _ => part_report.answer.to_string(),
+                };
+
+                println!(
+                    "  · Part {}: {}{} ({})",
+                    part,
+                    answer,
+                    verdict,
+                    with_alloc(part_report.time_ms, &part_report.alloc)
+                );
+            }
+        }
+        println!("  · Elapsed: {}", with_alloc(self.total_ms, &self.alloc));
+        self.print_artifacts();
+    }
+
+    /// A single file is shown as is, more are counted in the directory they share
+    fn print_artifacts(&self) {
+        match self.artifacts.as_slice() {
+            [] => {}
+            [file] => println!("  · Artifact: {}", file.display().dimmed()),
+            [first, ..] => {
+                let dir = first
+                    .ancestors()
+                    .skip(1)
+                    .find(|dir| self.artifacts.iter().all(|file| file.starts_with(dir)))
+                    .unwrap_or(Path::new(""));
+                println!(
+                    "  · Artifacts: {} files in {}",
+                    self.artifacts.len(),
+                    dir.display().dimmed()
+                );
+            }
+        }
+    }
+}
+
+impl Report {
+    pub fn push(&mut self, day_report: DayReport) {
+        self.total_ms += day_report.total_ms;
+        self.days.push(day_report);
+    }
+
+    /// Number of days that could not be solved, without the ones that timed out
+    pub fn failures(&self) -> usize {
+        self.days
+            .iter()
+            .filter(|day| day.error.is_some() && !day.timed_out)
+            .count()
+    }
+
+    pub fn timeouts(&self) -> usize {
+        self.days.iter().filter(|day| day.timed_out).count()
+    }
+
+    /// Time spent solving the days of each year, in the order of the years
+    pub fn year_totals(&self) -> BTreeMap<u16, f64> {
+        let mut totals = BTreeMap::new();
+        for day in &self.days {
+            *totals.entry(day.year).or_default() += day.total_ms;
+        }
+        totals
+    }
+
+    /// Prints the report once every day has run, the text format is printed day by day with
+    /// [`DayReport::print_text`] so only the total is left
+    pub fn print(&self, format: Format) -> Result<(), String> {
+        match format {
+            Format::Text => {
+                let year_totals = self.year_totals();
+                if year_totals.len() > 1 {
+                    for (year, total_ms) in year_totals {
+                        println!("Runtime of {year}: {total_ms:.4} ms");
+                    }
+                }
+                println!("Total runtime: {:.4} ms", self.total_ms);
+                println!("Wall-clock time: {:.4} ms", self.wall_ms);
+            }
+            Format::Json => println!("{}", self.to_json()?),
+            Format::Csv => print!("{}", self.to_csv()?),
+        }
+
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Could not write the report: {e}"))
+    }
+
+    pub fn to_csv(&self) -> Result<String, String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+
+        let status = |part: &Option<PartReport>| {
+            part.as_ref()
+                .and_then(|p| p.verdict.as_ref())
+                .map(|verdict| match verdict {
+                    Verdict::Correct => "correct",
+                    Verdict::Wrong { .. } => "wrong",
+                    Verdict::Unknown => "unknown",
+                })
+        };
+
+        for day in &self.days {
+            writer
+                .serialize(CsvRow {
+                    year: day.year,
+                    day: day.day,
+                    input_hash: day.input_hash.as_deref(),
+                    parse_ms: day.parse_ms,
+                    part1: day.part1.as_ref().map(|p| &p.answer),
+                    part1_ms: day.part1.as_ref().map(|p| p.time_ms),
+                    part1_status: status(&day.part1),
+                    part2: day.part2.as_ref().map(|p| &p.answer),
+                    part2_ms: day.part2.as_ref().map(|p| p.time_ms),
+                    part2_status: status(&day.part2),
+                    total_ms: day.total_ms,
+                    timed_out: day.timed_out,
+                    error: day.error.as_deref(),
+                    alloc_bytes: day.alloc.map(|a| a.bytes),
+                    alloc_count: day.alloc.map(|a| a.count),
+                    peak_bytes: day.alloc.map(|a| a.peak),
+                    artifacts: day.artifacts.len(),
+                })
+                .map_err(|e| format!("Could not write the report: {e}"))?;
+        }
+
+        let csv = writer
+            .into_inner()
+            .map_err(|e| format!("Could not write the report: {e}"))?;
+
+        String::from_utf8(csv).map_err(|e| format!("Could not write the report: {e}"))
+    }
+}
+
+/// Hex encoded SHA-256 of the input, matches the output of `sha256sum`
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// A duration followed by what was allocated meanwhile, if it was counted
+fn with_alloc(ms: f64, alloc: &Option<AllocStats>) -> String {
+    match alloc {
+        Some(alloc) => format!("{ms:.4} ms, {alloc}"),
+        None => format!("{ms:.4} ms"),
+    }
+}
+
+pub fn as_ms(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use advent_of_code::etc::AocError;
+
+    fn report() -> Report {
+        let mut report = Report::default();
+        report.push(DayReport {
+            year: 2024,
+            day: 1,
+            input_hash: Some(input_hash("1 2")),
+            parse_ms: 1.0,
+            parse_alloc: None,
+            part1: Some(PartReport {
+                answer: Solution::from(11_u64),
+                time_ms: 2.0,
+                alloc: Some(AllocStats {
+                    bytes: 2048,
+                    count: 3,
+                    peak: 1024,
+                    retained: 0,
+                }),
+                verdict: Some(Verdict::Correct),
+            }),
+            part2: None,
+            total_ms: 3.0,
+            alloc: Some(AllocStats {
+                bytes: 4096,
+                count: 4,
+                peak: 2048,
+                retained: 1024,
+            }),
+            error: None,
+            timed_out: false,
+            artifacts: vec![],
+        });
+        report.push(DayReport {
+            year: 2024,
+            day: 14,
+            input_hash: Some(input_hash("")),
+            parse_ms: 0.5,
+            parse_alloc: None,
+            part1: None,
+            part2: Some(PartReport {
+                answer: Solution::from("a, b"),
+                time_ms: 0.5,
+                alloc: None,
+                verdict: None,
+            }),
+            total_ms: 1.0,
+            alloc: None,
+            error: None,
+            timed_out: false,
+            artifacts: vec![
+                PathBuf::from("out/1-1/2024/day14/1.png"),
+                PathBuf::from("out/1-1/2024/day14/2.png"),
+            ],
+        });
+        report.push(DayReport::failed(
+            2024,
+            15,
+            None,
+            AocError::invalid_input("there is no robot in the warehouse"),
+        ));
+        report.push(DayReport {
+            timed_out: true,
+            ..DayReport::failed(
+                2023,
+                16,
+                Some(""),
+                AocError::TimedOut(Duration::from_secs(2)),
+            )
+        });
+        report
+    }
+
+    #[test]
+    fn verify_and_record() {
+        let mut answers = Answers::default();
+        let mut report = report();
+        // Only the first part of day 16 is solved, the second one has a placeholder answer
+        let mut part_one = DayReport {
+            day: 16,
+            part2: report.days[0].part1.clone().map(|part_report| PartReport {
+                verdict: None,
+                ..part_report
+            }),
+            ..report.days[0].clone()
+        };
+
+        report.days[0].record(&mut answers);
+        assert_eq!(answers.get(2024, 1, Part::One), Some("11"));
+        assert_eq!(answers.get(2024, 1, Part::Two), None);
+
+        answers.set(2024, 14, Part::Two, &Solution::from("a"));
+        assert_eq!(report.days[0].verify(&answers), 0);
+        assert_eq!(report.days[1].verify(&answers), 1);
+        assert_eq!(
+            report.days[1].part2.as_ref().unwrap().verdict,
+            Some(Verdict::Wrong {
+                expected: String::from("a")
+            })
+        );
+
+        part_one.record(&mut answers);
+        assert_eq!(answers.get(2024, 16, Part::One), Some("11"));
+        assert_eq!(answers.get(2024, 16, Part::Two), None);
+        assert_eq!(part_one.verify(&answers), 0);
+        assert_eq!(part_one.part2.unwrap().verdict, None);
+    }
+
+    #[test]
+    fn hash() {
+        assert_eq!(
+            input_hash(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let json: serde_json::Value = serde_json::from_str(&report().to_json().unwrap()).unwrap();
+
+        assert_eq!(json["total_ms"], 4.0);
+        assert_eq!(json["wall_ms"], 0.0);
+        assert_eq!(json["days"][0]["part1"]["answer"], 11);
+        assert_eq!(json["days"][0]["part1"]["verdict"]["status"], "correct");
+        assert_eq!(json["days"][0]["part1"]["alloc"]["count"], 3);
+        assert_eq!(json["days"][0]["alloc"]["peak"], 2048);
+        assert_eq!(json["days"][0]["part2"], serde_json::Value::Null);
+        assert_eq!(json["days"][1].get("alloc"), None);
+        assert_eq!(json["days"][1]["part2"]["answer"], "a, b");
+        assert_eq!(json["days"][1].get("error"), None);
+        assert_eq!(json["days"][1]["artifacts"][1], "out/1-1/2024/day14/2.png");
+        assert_eq!(json["days"][0].get("artifacts"), None);
+        assert_eq!(json["days"][2]["input_hash"], serde_json::Value::Null);
+        assert_eq!(
+            json["days"][2]["error"],
+            "invalid input: there is no robot in the warehouse"
+        );
+        assert_eq!(json["days"][2]["timed_out"], false);
+        assert_eq!(json["days"][3]["timed_out"], true);
+        assert_eq!(json["days"][3]["year"], 2023);
+    }
+
+    #[test]
+    fn year_totals() {
+        assert_eq!(
+            report().year_totals().into_iter().collect::<Vec<_>>(),
+            [(2023, 0.), (2024, 4.)]
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let csv = report().to_csv().unwrap();
+        let mut lines = csv.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some(
+                "year,day,input_hash,parse_ms,part1,part1_ms,part1_status,\
+                part2,part2_ms,part2_status,total_ms,timed_out,error,\
+                alloc_bytes,alloc_count,peak_bytes,artifacts"
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                format!(
+                    "2024,1,{},1.0,11,2.0,correct,,,,3.0,false,,4096,4,2048,0",
+                    input_hash("1 2")
+                )
+                .as_str()
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                format!(
+                    "2024,14,{},0.5,,,,\"a, b\",0.5,,1.0,false,,,,,2",
+                    input_hash("")
+                )
+                .as_str()
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                "2024,15,,0.0,,,,,,,0.0,false,invalid input: there is no robot in the warehouse,,,,0"
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                format!(
+                    "2023,16,{},0.0,,,,,,,0.0,true,timed out after 2s,,,,0",
+                    input_hash("")
+                )
+                .as_str()
+            )
+        );
+    }
+}
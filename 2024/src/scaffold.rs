@@ -0,0 +1,234 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use advent_of_code::input;
+
+/// A day that is not solved yet, `NN` is the padded day, `DAY` the day, `YEAR` the year and
+/// `TITLE` the string literal of the title of the puzzle
+const STUB_TEMPLATE: &str = "use crate::{register_day, AocError, Context, Day, Solution};
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub struct DayNN;
+
+register_day!(DayNN, YEAR, DAY, TITLE, Stub);
+
+impl Day for DayNN {
+    type Parsed = ();
+
+    fn parse(_input: &str) -> Result<Self::Parsed, AocError> {
+        Ok(())
+    }
+
+    fn part1(_parsed: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        // Your solution here...
+        let sol1: u64 = 0;
+
+        Ok(Solution::from(sol1))
+    }
+
+    fn part2(_parsed: &Self::Parsed, _ctx: &Context) -> Result<Solution, AocError> {
+        // Your solution here...
+        let sol2: u64 = 0;
+
+        Ok(Solution::from(sol2))
+    }
+}
+";
+
+const TEST_TEMPLATE: &str = "
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = \"\";
+        let (p1, p2) = DayNN::solve(input).unwrap();
+        assert_eq!(p1, Solution::from(0_u64));
+        assert_eq!(p2, Solution::from(0_u64));
+    }
+}
+";
+
+/// The source of a new day, a registered stub with a test module
+pub fn day_source(year: u16, day: u8, title: &str) -> String {
+    format!("{STUB_TEMPLATE}{TEST_TEMPLATE}")
+        .replace("NN", &format!("{:02}", day))
+        .replace("YEAR", &year.to_string())
+        .replace("DAY", &day.to_string())
+        // Escaped like a Rust string, so that quotes or line breaks still compile
+        .replace("TITLE", &format!("{title:?}"))
+}
+
+/// Whether a source file is still the template, with or without a test module, so that it can
+/// be replaced. The registration is not compared since the title may differ
+pub fn is_stub(source: &str, day: u8) -> bool {
+    let without_registration = |source: &str| {
+        source
+            .lines()
+            .filter(|line| !line.starts_with("register_day!("))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_end()
+            .to_owned()
+    };
+    let source = without_registration(source);
+
+    [
+        STUB_TEMPLATE.to_owned(),
+        format!("{STUB_TEMPLATE}{TEST_TEMPLATE}"),
+    ]
+    .iter()
+    .any(|template| without_registration(&template.replace("NN", &format!("{:02}", day))) == source)
+}
+
+/// Adds `pub mod dayNN;` to `days/mod.rs`, `None` if it is already there
+pub fn register_module(mod_rs: &str, day: u8) -> Option<String> {
+    let line = format!("pub mod day{:02};", day);
+    if mod_rs.lines().any(|l| l.trim() == line) {
+        return None;
+    }
+
+    let mut lines: Vec<&str> = mod_rs.lines().collect();
+    // The modules are sorted, and so are their names since the days have two digits
+    let idx = lines
+        .iter()
+        .position(|l| l.starts_with("pub mod day") && l.trim() > line.as_str())
+        .unwrap_or(lines.len());
+    lines.insert(idx, &line);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Creates the registered source of a day, declares its module in `days/mod.rs` and creates an
+/// empty example. Returns the files that were written
+pub fn scaffold(
+    src_dir: &Path,
+    examples_dir: &Path,
+    year: u16,
+    day: u8,
+    title: &str,
+) -> Result<Vec<PathBuf>, String> {
+    let day_path = src_dir.join("days").join(format!("day{:02}.rs", day));
+    let mod_path = src_dir.join("days").join("mod.rs");
+    let example_path = input::example_path(examples_dir, year, day, 1);
+
+    match fs::read_to_string(&day_path) {
+        Ok(source) if !is_stub(&source, day) => {
+            return Err(format!(
+                "Day {:02} is already solved in {}, it is not replaced",
+                day,
+                day_path.display()
+            ))
+        }
+        Ok(_) => {}
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(format!("Could not read {}: {e}", day_path.display())),
+    }
+
+    let mod_rs = fs::read_to_string(&mod_path)
+        .map_err(|e| format!("Could not read {}: {e}", mod_path.display()))?;
+    let mod_rs = register_module(&mod_rs, day);
+
+    let mut written = vec![];
+    let mut write = |path: &Path, content: &str| {
+        fs::write(path, content).map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+        written.push(path.to_owned());
+        Ok::<_, String>(())
+    };
+
+    write(&day_path, &day_source(year, day, title))?;
+    if let Some(mod_rs) = mod_rs {
+        write(&mod_path, &mod_rs)?;
+    }
+    if !example_path.exists() {
+        if let Some(dir) = example_path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+        }
+        write(&example_path, "")?;
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn stubs() {
+        assert!(is_stub(&day_source(2024, 17, "Chronospatial Computer"), 17));
+        assert!(is_stub(&day_source(2024, 17, ""), 17));
+        assert!(is_stub(include_str!("days/day17.rs"), 17));
+        assert!(!is_stub(&day_source(2024, 17, ""), 18));
+        assert!(!is_stub(
+            &day_source(2024, 17, "").replace("let sol1: u64 = 0;", "let sol1: u64 = 42;"),
+            17
+        ));
+        assert!(!is_stub(include_str!("days/day16.rs"), 16));
+
+        assert!(day_source(2024, 5, "Say \"hi\"")
+            .contains("register_day!(Day05, 2024, 5, \"Say \\\"hi\\\"\", Stub);"));
+        assert!(day_source(2024, 5, "Line\nbreak\\")
+            .contains("register_day!(Day05, 2024, 5, \"Line\\nbreak\\\\\", Stub);"));
+    }
+
+    #[test]
+    fn register() {
+        assert_eq!(
+            register_module("pub mod day01;\npub mod day03;\n", 2),
+            Some(String::from(
+                "pub mod day01;\npub mod day02;\npub mod day03;\n"
+            ))
+        );
+        assert_eq!(
+            register_module("pub mod day01;\n", 12),
+            Some(String::from("pub mod day01;\npub mod day12;\n"))
+        );
+        assert_eq!(register_module("pub mod day01;\n", 1), None);
+    }
+
+    #[test]
+    fn scaffold_day() {
+        let dir = temp_dir("scaffold");
+        let (src, examples) = (dir.join("src"), dir.join("examples"));
+        fs::create_dir_all(src.join("days")).unwrap();
+        fs::write(src.join("days/mod.rs"), "pub mod day01;\npub mod day16;\n").unwrap();
+
+        let written = scaffold(&src, &examples, 2024, 15, "Warehouse Woes").unwrap();
+        assert_eq!(written.len(), 3);
+        assert_eq!(
+            fs::read_to_string(src.join("days/day15.rs")).unwrap(),
+            day_source(2024, 15, "Warehouse Woes")
+        );
+        assert_eq!(
+            fs::read_to_string(src.join("days/mod.rs")).unwrap(),
+            "pub mod day01;\npub mod day15;\npub mod day16;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(examples.join("2024/day15/1.txt")).unwrap(),
+            ""
+        );
+
+        // A stub is replaced, but there is nothing left to declare
+        assert_eq!(
+            scaffold(&src, &examples, 2024, 15, ""),
+            Ok(vec![src.join("days/day15.rs")])
+        );
+
+        fs::write(src.join("days/day15.rs"), "pub struct Day15;\n").unwrap();
+        assert!(scaffold(&src, &examples, 2024, 15, "").is_err());
+        assert_eq!(
+            fs::read_to_string(src.join("days/day15.rs")).unwrap(),
+            "pub struct Day15;\n"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+/// First and last day of the advent calendar
+pub const DAYS: RangeInclusive<u8> = 1..=25;
+
+/// Selects which days to run from the command line:
+/// - `N` a single day
+/// - `A..=B`, `A..B`, `A..`, `..=B` a range of days
+/// - `all` every day of the calendar
+/// - `solved` every day that is not a stub
+/// - `!SELECTOR` excludes the days of another selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelector {
+    Day(u8),
+    Range(RangeInclusive<u8>),
+    All,
+    Solved,
+    Exclude(Box<DaySelector>),
+}
+
+impl DaySelector {
+    /// Returns the days matched by the selector, `is_solved` tells if a day has a solution
+    fn days(&self, is_solved: impl Fn(u8) -> bool) -> Vec<u8> {
+        match self {
+            DaySelector::Day(day) => vec![*day],
+            DaySelector::Range(range) => range.clone().collect(),
+            DaySelector::All => DAYS.collect(),
+            DaySelector::Solved => DAYS.filter(|&day| is_solved(day)).collect(),
+            DaySelector::Exclude(selector) => selector.days(is_solved),
+        }
+    }
+}
+
+/// Resolves a list of selectors into the days to run, keeping the order in which they were
+/// first selected.
+///
+/// Exclusions are applied after every other selector, if only exclusions are given they are
+/// applied to the solved days.
+pub fn resolve(selectors: &[DaySelector], is_solved: impl Fn(u8) -> bool + Copy) -> Vec<u8> {
+    let (excluded, included): (Vec<&DaySelector>, Vec<&DaySelector>) = selectors
+        .iter()
+        .partition(|s| matches!(s, DaySelector::Exclude(_)));
+
+    let included = match included.is_empty() {
+        true => DaySelector::Solved.days(is_solved),
+        false => included.iter().flat_map(|s| s.days(is_solved)).collect(),
+    };
+    let excluded: Vec<u8> = excluded.iter().flat_map(|s| s.days(is_solved)).collect();
+
+    let mut days: Vec<u8> = Vec::with_capacity(included.len());
+    for day in included {
+        if !excluded.contains(&day) && !days.contains(&day) {
+            days.push(day);
+        }
+    }
+
+    days
+}
+
+impl FromStr for DaySelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(excluded) = s.strip_prefix('!') {
+            return match excluded.parse()? {
+                DaySelector::Exclude(_) => Err(format!("`{s}` excludes an exclusion")),
+                selector => Ok(DaySelector::Exclude(Box::new(selector))),
+            };
+        }
+
+        match s {
+            "all" => return Ok(DaySelector::All),
+            "solved" => return Ok(DaySelector::Solved),
+            _ => {}
+        }
+
+        if let Some((start, end)) = s.split_once("..") {
+            let start = match start {
+                "" => *DAYS.start(),
+                _ => parse_day(start)?,
+            };
+            let end = match end.strip_prefix('=') {
+                Some(end) => parse_day(end)?,
+                None if end.is_empty() => *DAYS.end(),
+                None => parse_day(end)?
+                    .checked_sub(1)
+                    .filter(|end| *end >= *DAYS.start())
+                    .ok_or_else(|| format!("`{s}` does not contain any day"))?,
+            };
+
+            if start > end {
+                return Err(format!("`{s}` does not contain any day"));
+            }
+
+            return Ok(DaySelector::Range(start..=end));
+        }
+
+        parse_day(s).map(DaySelector::Day)
+    }
+}
+
+/// Parses a day number, only the days of the advent calendar are accepted
+pub fn parse_day(arg: &str) -> Result<u8, String> {
+    let day: u8 = arg
+        .trim()
+        .parse()
+        .map_err(|_| format!("`{arg}` is not a day number, a range, `all` or `solved`"))?;
+
+    if !DAYS.contains(&day) {
+        return Err(format!(
+            "day {day} is not between {} and {}",
+            DAYS.start(),
+            DAYS.end()
+        ));
+    }
+
+    Ok(day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solved(day: u8) -> bool {
+        day <= 16
+    }
+
+    fn selectors(args: &[&str]) -> Vec<DaySelector> {
+        args.iter().map(|a| a.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn parse_selectors() {
+        assert_eq!("1".parse(), Ok(DaySelector::Day(1)));
+        assert_eq!("1..=16".parse(), Ok(DaySelector::Range(1..=16)));
+        assert_eq!("1..16".parse(), Ok(DaySelector::Range(1..=15)));
+        assert_eq!("20..".parse(), Ok(DaySelector::Range(20..=25)));
+        assert_eq!("..=3".parse(), Ok(DaySelector::Range(1..=3)));
+        assert_eq!("all".parse(), Ok(DaySelector::All));
+        assert_eq!("solved".parse(), Ok(DaySelector::Solved));
+        assert_eq!(
+            "!14".parse(),
+            Ok(DaySelector::Exclude(Box::new(DaySelector::Day(14))))
+        );
+    }
+
+    #[test]
+    fn invalid_selectors() {
+        assert!("0".parse::<DaySelector>().is_err());
+        assert!("26".parse::<DaySelector>().is_err());
+        assert!("1..=26".parse::<DaySelector>().is_err());
+        assert!("5..=3".parse::<DaySelector>().is_err());
+        assert!("1..1".parse::<DaySelector>().is_err());
+        assert!("!!3".parse::<DaySelector>().is_err());
+        assert!("day3".parse::<DaySelector>().is_err());
+    }
+
+    #[test]
+    fn resolve_selectors() {
+        assert_eq!(resolve(&selectors(&["3", "1", "3"]), solved), vec![3, 1]);
+        assert_eq!(
+            resolve(&selectors(&["all"]), solved),
+            (1..=25).collect::<Vec<u8>>()
+        );
+        assert_eq!(
+            resolve(&selectors(&["solved"]), solved),
+            (1..=16).collect::<Vec<u8>>()
+        );
+        assert_eq!(
+            resolve(&selectors(&["!14", "10..=15"]), solved),
+            vec![10, 11, 12, 13, 15]
+        );
+        assert_eq!(resolve(&selectors(&["!1..=14"]), solved), vec![15, 16]);
+    }
+}
@@ -0,0 +1,424 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use advent_of_code::etc::cancel::CancelToken;
+use advent_of_code::etc::{registry, Part};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::answers::{day_key, part_key};
+use crate::client::Client;
+
+/// What the website answered to a submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+        /// Time before the website accepts another answer
+        wait: Duration,
+    },
+    /// An answer was submitted too recently, nothing was checked
+    Wait(Duration),
+    /// The part was already solved, or its first part was not
+    AlreadySolved,
+    /// A page that could not be understood, as text
+    Unknown(String),
+}
+
+/// Whether a wrong answer was too high or too low, which rules out more answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Outcome {
+    /// Reads the outcome from the page returned by the website, only its `<article>` matters
+    pub fn parse(page: &str) -> Self {
+        let article = match (page.find("<article>"), page.find("</article>")) {
+            (Some(start), Some(end)) if start < end => &page[start..end],
+            _ => page,
+        };
+        let tag_regex = Regex::new(r"<[^>]*>").unwrap();
+        let retry_regex = Regex::new(r"wait (one|\d+) minutes? before trying again").unwrap();
+        let wait_regex = Regex::new(r"You have (?:(\d+)m)? ?(?:(\d+)s)? left to wait").unwrap();
+
+        let text = tag_regex.replace_all(article, "");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if text.contains("That's the right answer") {
+            Outcome::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = if text.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if text.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            let minutes = retry_regex
+                .captures(&text)
+                .map_or(1, |captures| match &captures[1] {
+                    "one" => 1,
+                    n => n.parse().unwrap_or(1),
+                });
+
+            Outcome::Wrong {
+                hint,
+                wait: Duration::from_secs(minutes * 60),
+            }
+        } else if let Some(captures) = wait_regex.captures(&text) {
+            let number = |i| {
+                captures
+                    .get(i)
+                    .map_or(0, |m: regex::Match| m.as_str().parse().unwrap_or(0))
+            };
+
+            Outcome::Wait(Duration::from_secs(number(1) * 60 + number(2)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown(text)
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer"),
+            Outcome::Wrong { hint, wait } => {
+                write!(f, "That's not the right answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", it is too high")?,
+                    Some(Hint::TooLow) => write!(f, ", it is too low")?,
+                    None => {}
+                }
+                write!(f, ". Wait {}s before trying again", wait.as_secs())
+            }
+            Outcome::Wait(wait) => write!(
+                f,
+                "An answer was submitted too recently, wait {}s before trying again",
+                wait.as_secs()
+            ),
+            Outcome::AlreadySolved => {
+                write!(f, "This part cannot be submitted, it may already be solved")
+            }
+            Outcome::Unknown(text) => write!(f, "Unexpected answer from the website: {text}"),
+        }
+    }
+}
+
+/// The default file of the submissions, next to the stored answers in the crate
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("submissions.toml")
+}
+
+/// The answers rejected by the website and when it accepts submissions again:
+/// ```toml
+/// wait_until = 1733040000
+///
+/// [[rejected.2024.day01.part1]]
+/// answer = "12"
+/// hint = "too-high"
+/// ```
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Submissions {
+    /// Seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wait_until: Option<u64>,
+    #[serde(default)]
+    rejected: BTreeMap<String, BTreeMap<String, BTreeMap<String, Vec<Rejected>>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rejected {
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+impl Submissions {
+    /// Loads the submissions from a TOML file, a missing file has none
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Submissions::default()),
+            Err(e) => return Err(format!("Could not read {}: {e}", path.display())),
+        };
+
+        toml::from_str(&content).map_err(|e| format!("Could not parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = toml::to_string(self)
+            .map_err(|e| format!("Could not serialize the submissions: {e}"))?;
+
+        fs::write(path, content).map_err(|e| format!("Could not write {}: {e}", path.display()))
+    }
+
+    pub fn rejected(&self, year: u16, day: u8, part: Part) -> &[Rejected] {
+        self.rejected
+            .get(&year.to_string())
+            .and_then(|days| days.get(&day_key(day)))
+            .and_then(|parts| parts.get(part_key(part)))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Fails if the answer must not be submitted, because of the cooldown or because the
+    /// website already rejected it, or a lower or higher answer that was too low or too high
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        now: u64,
+    ) -> Result<(), String> {
+        if let Some(left) = self.wait_until.and_then(|until| until.checked_sub(now)) {
+            if left > 0 {
+                return Err(format!(
+                    "The website asked to wait before submitting again, {left}s left"
+                ));
+            }
+        }
+
+        let number = answer.parse::<i128>().ok();
+        for rejected in self.rejected(year, day, part) {
+            if rejected.answer == answer {
+                return Err(format!("{answer} was already rejected"));
+            }
+
+            let Some((number, rejected_number)) = number.zip(rejected.answer.parse::<i128>().ok())
+            else {
+                continue;
+            };
+            match rejected.hint {
+                Some(Hint::TooHigh) if number >= rejected_number => {
+                    return Err(format!(
+                        "{answer} is too high, {} already was",
+                        rejected.answer
+                    ))
+                }
+                Some(Hint::TooLow) if number <= rejected_number => {
+                    return Err(format!(
+                        "{answer} is too low, {} already was",
+                        rejected.answer
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remembers a rejected answer and the cooldown asked by the website
+    pub fn record(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        outcome: &Outcome,
+        now: u64,
+    ) {
+        match outcome {
+            Outcome::Wrong { hint, wait } => {
+                self.rejected
+                    .entry(year.to_string())
+                    .or_default()
+                    .entry(day_key(day))
+                    .or_default()
+                    .entry(part_key(part).to_owned())
+                    .or_default()
+                    .push(Rejected {
+                        answer: answer.to_owned(),
+                        hint: *hint,
+                    });
+                self.wait_until = Some(now + wait.as_secs());
+            }
+            Outcome::Wait(wait) => self.wait_until = Some(now + wait.as_secs()),
+            Outcome::Correct | Outcome::AlreadySolved | Outcome::Unknown(_) => {}
+        }
+    }
+}
+
+/// Refuses the answers that can only be placeholders: the part has no solution yet, or its
+/// solver was cancelled before it finished
+pub fn check_answer(year: u16, day: u8, part: Part, cancel: &CancelToken) -> Result<(), String> {
+    if !registry::is_part_solved(year, day, part) {
+        return Err(format!(
+            "Part {} of day {:02} of {year} is not solved yet",
+            part.number(),
+            day
+        ));
+    }
+    if cancel.is_cancelled() {
+        return Err(String::from(
+            "The solver was cancelled, its answer is not submitted",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Submits an answer unless [`Submissions::check`] rules it out, and records the outcome
+pub fn submit(
+    client: &mut Client,
+    submissions: &mut Submissions,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+    now: u64,
+) -> Result<Outcome, String> {
+    submissions.check(year, day, part, answer, now)?;
+
+    let page = client.submit(year, day, part, answer)?;
+    let outcome = Outcome::parse(&page);
+    submissions.record(year, day, part, answer, &outcome, now);
+
+    Ok(outcome)
+}
+
+/// Seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::stub;
+
+    const RIGHT: &str =
+        "<main>\n<article><p>That's the right answer!  You are <em>one gold star</em> closer to \
+        finding the Chief Historian.</p></article>\n</main>";
+    const TOO_HIGH: &str =
+        "<main>\n<article><p>That's not the right answer; your answer is too high.  \
+        If you're stuck, make sure you're using the full input data. Please wait one minute before \
+        trying again. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+    const WAIT: &str =
+        "<main>\n<article><p>You gave an answer too recently; you have to wait after \
+        submitting an answer before trying again.  You have 1m 5s left to wait. \
+        <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>\n</main>";
+
+    #[test]
+    fn refuse_placeholders() {
+        let cancel = CancelToken::new();
+        assert!(check_answer(2024, 1, Part::Two, &cancel).is_ok());
+        assert!(check_answer(2024, 16, Part::One, &cancel).is_ok());
+        assert!(check_answer(2024, 16, Part::Two, &cancel).is_err());
+        assert!(check_answer(2024, 25, Part::One, &cancel).is_err());
+
+        cancel.cancel();
+        assert!(check_answer(2024, 1, Part::Two, &cancel).is_err());
+    }
+
+    #[test]
+    fn parse_outcomes() {
+        assert_eq!(Outcome::parse(RIGHT), Outcome::Correct);
+        assert_eq!(
+            Outcome::parse(TOO_HIGH),
+            Outcome::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait: Duration::from_secs(60)
+            }
+        );
+        assert_eq!(
+            Outcome::parse(
+                "<article><p>That's not the right answer. Please wait 5 minutes before trying again.</p></article>"
+            ),
+            Outcome::Wrong {
+                hint: None,
+                wait: Duration::from_secs(300)
+            }
+        );
+        assert_eq!(Outcome::parse(WAIT), Outcome::Wait(Duration::from_secs(65)));
+        assert_eq!(
+            Outcome::parse("<article><p>You have 38s left to wait.</p></article>"),
+            Outcome::Wait(Duration::from_secs(38))
+        );
+        assert_eq!(
+            Outcome::parse(
+                "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"
+            ),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(
+            Outcome::parse("<article><p>Something else</p></article>"),
+            Outcome::Unknown(String::from("Something else"))
+        );
+    }
+
+    #[test]
+    fn rejected_answers() {
+        let mut submissions = Submissions::default();
+        let too_high = Outcome::Wrong {
+            hint: Some(Hint::TooHigh),
+            wait: Duration::from_secs(60),
+        };
+        submissions.record(2024, 1, Part::One, "100", &too_high, 1000);
+
+        assert!(submissions
+            .check(2024, 1, Part::One, "50", 1030)
+            .is_err_and(|e| e.contains("30s left")));
+        assert!(submissions.check(2024, 1, Part::One, "50", 1060).is_ok());
+        assert!(submissions.check(2024, 1, Part::One, "100", 1060).is_err());
+        assert!(submissions.check(2024, 1, Part::One, "101", 1060).is_err());
+        assert!(submissions.check(2024, 1, Part::Two, "100", 1060).is_ok());
+
+        let wrong = Outcome::Wrong {
+            hint: None,
+            wait: Duration::from_secs(60),
+        };
+        submissions.record(2024, 1, Part::One, "abc", &wrong, 1100);
+        assert!(submissions.check(2024, 1, Part::One, "abc", 2000).is_err());
+        assert!(submissions.check(2024, 1, Part::One, "abd", 2000).is_ok());
+
+        let toml = toml::to_string(&submissions).unwrap();
+        assert_eq!(toml::from_str::<Submissions>(&toml).unwrap(), submissions);
+    }
+
+    #[test]
+    fn submit_once() {
+        let (url, requests) = stub::serve(vec![(200, TOO_HIGH), (200, RIGHT)]);
+        let mut client = Client::new(&url, "abc", Duration::ZERO);
+        let mut submissions = Submissions::default();
+
+        assert_eq!(
+            submit(&mut client, &mut submissions, 2024, 1, Part::Two, "100", 0),
+            Ok(Outcome::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait: Duration::from_secs(60)
+            })
+        );
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/2024/day/1/answer");
+        assert_eq!(request.body, "level=2&answer=100");
+        assert_eq!(request.header("cookie"), Some("session=abc"));
+
+        // Neither the cooldown nor the rejected answer reach the website
+        assert!(submit(&mut client, &mut submissions, 2024, 1, Part::Two, "90", 10).is_err());
+        assert!(submit(&mut client, &mut submissions, 2024, 1, Part::Two, "100", 60).is_err());
+
+        assert_eq!(
+            submit(&mut client, &mut submissions, 2024, 1, Part::Two, "90", 60),
+            Ok(Outcome::Correct)
+        );
+        assert_eq!(requests.recv().unwrap().body, "level=2&answer=90");
+    }
+}
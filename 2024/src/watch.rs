@@ -0,0 +1,266 @@
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use owo_colors::OwoColorize;
+use serde_json::Value;
+
+/// The modification time of every watched file, the files of a watched directory included.
+/// A missing file has no time so that creating it counts as a change
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// The answer and time of a part in a run of the solver
+#[derive(Debug, Clone, PartialEq)]
+struct PartAnswer {
+    answer: String,
+    time_ms: f64,
+}
+
+/// What a day is rerun with when one of its files changes
+#[derive(Debug)]
+pub struct Watch {
+    pub year: u16,
+    pub day: u8,
+    /// Files and directories whose changes rerun the day
+    pub paths: Vec<PathBuf>,
+    /// The runner itself, which `run`s the day. Taken before anything is rebuilt, since the
+    /// path of a replaced executable is not the one of the new build anymore
+    pub exe: PathBuf,
+    /// Arguments of `run` after the day, that select its input
+    pub run_args: Vec<OsString>,
+    /// Whether to run the tests of the day before solving it
+    pub test: bool,
+    pub interval: Duration,
+}
+
+impl Watch {
+    /// Reruns the day every time a file changes, until Ctrl-C is pressed
+    pub fn run(&self) -> Result<(), String> {
+        println!(
+            "Watching day {:02} of {}, Ctrl-C to stop",
+            self.day, self.year
+        );
+        for path in &self.paths {
+            println!("  · {}", path.display().dimmed());
+        }
+
+        let mut previous = [None, None];
+        let mut seen = snapshot(&self.paths);
+        loop {
+            previous = self.rerun(&previous)?;
+
+            let changed = loop {
+                thread::sleep(self.interval);
+                let current = snapshot(&self.paths);
+                let changed = changes(&seen, &current);
+                if !changed.is_empty() {
+                    seen = current;
+                    break changed;
+                }
+            };
+
+            println!();
+            for path in changed {
+                println!("{} {}", "Changed".bold(), path.display());
+            }
+        }
+    }
+
+    /// Runs the tests through cargo, builds the changed source again and runs the solver with
+    /// the new build, then prints the answers next to the previous ones
+    fn rerun(&self, previous: &[Option<PartAnswer>; 2]) -> Result<[Option<PartAnswer>; 2], String> {
+        let cargo = |args: &[OsString]| {
+            Command::new("cargo")
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .args(args)
+                .output()
+                .map_err(|e| format!("Could not run cargo: {e}"))
+        };
+
+        if self.test {
+            let filter = format!("days::day{:02}::", self.day);
+            let output = cargo(&["test".into(), "--quiet".into(), "--".into(), filter.into()])?;
+            match output.status.success() {
+                true => println!("  · Tests: {}", "passed".green()),
+                false => {
+                    println!("  · Tests: {}", "failed".red());
+                    print!("{}", String::from_utf8_lossy(&output.stdout));
+                    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                }
+            }
+        }
+
+        // The runner is rebuilt with the profile and features it was built with
+        let mut build: Vec<OsString> = vec!["build".into(), "--quiet".into()];
+        if !cfg!(debug_assertions) {
+            build.push("--release".into());
+        }
+        if cfg!(feature = "memory") {
+            build.extend(["--features".into(), "memory".into()]);
+        }
+        let output = cargo(&build)?;
+        if !output.status.success() {
+            println!("  · {}", "Failed to build".red());
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            return Ok(previous.clone());
+        }
+
+        let output = Command::new(&self.exe)
+            .arg("run")
+            .arg(self.day.to_string())
+            .args(["--year", &self.year.to_string()])
+            .args(["--format", "json"])
+            .args(&self.run_args)
+            .output()
+            .map_err(|e| format!("Could not run {}: {e}", self.exe.display()))?;
+
+        let answers = match parse_run(&String::from_utf8_lossy(&output.stdout)) {
+            Ok(answers) => answers,
+            Err(e) => {
+                println!("  · {}", format!("Failed: {e}").red());
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                return Ok(previous.clone());
+            }
+        };
+
+        for (part, (answer, previous)) in answers.iter().zip(previous).enumerate() {
+            if let Some(PartAnswer { answer, time_ms }) = answer {
+                let change = match previous {
+                    Some(previous) if previous.answer == *answer => {
+                        format!(" {}", "unchanged".dimmed())
+                    }
+                    Some(previous) => format!(" {}", format!("was {}", previous.answer).yellow()),
+                    None => String::new(),
+                };
+                println!(
+                    "  · Part {}: {} ({:.4} ms){}",
+                    part + 1,
+                    answer,
+                    time_ms,
+                    change
+                );
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/// The modification times of the files, one level deep in the directories
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut snapshot = Snapshot::new();
+
+    for path in paths {
+        match fs::read_dir(path) {
+            Ok(entries) => {
+                for entry in entries.flatten() {
+                    snapshot.insert(entry.path(), modified(&entry.path()));
+                }
+            }
+            Err(_) => {
+                snapshot.insert(path.clone(), modified(path));
+            }
+        }
+    }
+
+    snapshot
+}
+
+/// The files that were created, changed or removed between two snapshots
+fn changes(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let created_or_changed = after
+        .iter()
+        .filter(|(path, time)| before.get(*path) != Some(time))
+        .map(|(path, _)| path);
+    let removed = before.keys().filter(|path| !after.contains_key(*path));
+
+    created_or_changed.chain(removed).cloned().collect()
+}
+
+/// The answers in the JSON report of a single day, or why it could not be solved
+fn parse_run(json: &str) -> Result<[Option<PartAnswer>; 2], String> {
+    let report: Value =
+        serde_json::from_str(json).map_err(|_| String::from("the day could not be run"))?;
+    let day = &report["days"][0];
+
+    if let Some(error) = day["error"].as_str() {
+        return Err(error.to_owned());
+    }
+
+    let part = |key: &str| {
+        let part = day[key].as_object()?;
+        let answer = match &part["answer"] {
+            Value::String(answer) => answer.clone(),
+            answer => answer.to_string(),
+        };
+
+        Some(PartAnswer {
+            answer,
+            time_ms: part["time_ms"].as_f64().unwrap_or_default(),
+        })
+    };
+
+    Ok([part("part1"), part("part2")])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn detect_changes() {
+        let dir = temp_dir("watch");
+        fs::create_dir_all(dir.join("examples")).unwrap();
+        let (source, examples) = (dir.join("day01.rs"), dir.join("examples"));
+        let paths = [source.clone(), examples.clone()];
+
+        let before = snapshot(&paths);
+        assert_eq!(before, Snapshot::from([(source.clone(), None)]));
+
+        fs::write(&source, "").unwrap();
+        fs::write(examples.join("1.txt"), "").unwrap();
+        let after = snapshot(&paths);
+        assert_eq!(
+            changes(&before, &after),
+            [source.clone(), examples.join("1.txt")]
+        );
+        assert!(changes(&after, &after).is_empty());
+
+        fs::remove_file(examples.join("1.txt")).unwrap();
+        assert_eq!(changes(&after, &snapshot(&paths)), [examples.join("1.txt")]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn parse_report() {
+        let json = r#"{"days": [{"day": 1, "part1": {"answer": 11, "time_ms": 0.5},
+            "part2": {"answer": "a,b", "time_ms": 1.5}}]}"#;
+        assert_eq!(
+            parse_run(json),
+            Ok([
+                Some(PartAnswer {
+                    answer: String::from("11"),
+                    time_ms: 0.5,
+                }),
+                Some(PartAnswer {
+                    answer: String::from("a,b"),
+                    time_ms: 1.5,
+                }),
+            ])
+        );
+
+        let json = r#"{"days": [{"day": 1, "part1": null, "part2": null,
+            "error": "invalid input: empty"}]}"#;
+        assert_eq!(parse_run(json), Err(String::from("invalid input: empty")));
+        assert!(parse_run("").is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use advent_of_code::days::day11::Day11;
+use advent_of_code::etc::registry;
+use advent_of_code::{Context, Day, Part, Solution};
+
+#[test]
+fn solve_a_day() {
+    let (p1, _) = Day11::solve("125 17").unwrap();
+    assert_eq!(p1, Solution::from(55312_u64));
+}
+
+#[test]
+fn run_a_registered_day() {
+    let info = registry::get(2024, 11).unwrap();
+    assert_eq!(info.title, "Plutonian Pebbles");
+
+    let run = (info.run)("125 17", Some(Part::One), &Context::default()).unwrap();
+    assert_eq!(run.part1.unwrap().solution, Solution::from(55312_u64));
+    assert!(run.part2.is_none());
+}